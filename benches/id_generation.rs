@@ -1,4 +1,5 @@
-use banuid::IdGenerator;
+use banuid::{CachedClock, IdGenerator};
+use std::time::Duration;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 fn bench_id_generation(c: &mut Criterion) {
@@ -11,6 +12,20 @@ fn bench_id_generation(c: &mut Criterion) {
     });
 }
 
+fn bench_cached_clock_generation(c: &mut Criterion) {
+    let generator = IdGenerator::builder()
+        .time_source(CachedClock::spawn(Duration::from_millis(1)))
+        .shard_id(42)
+        .build()
+        .unwrap();
+
+    c.bench_function("id_generation_cached_clock", |b| {
+        b.iter(|| {
+            black_box(generator.next_id());
+        });
+    });
+}
+
 fn bench_id_creation(c: &mut Criterion) {
     c.bench_function("id_creation", |b| {
         b.iter(|| {
@@ -58,6 +73,7 @@ fn bench_concurrent_generation(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_id_generation,
+    bench_cached_clock_generation,
     bench_id_creation,
     bench_extract_operations,
     bench_concurrent_generation
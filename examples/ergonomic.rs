@@ -1,5 +1,3 @@
-use banuid;
-
 fn main() {
     // Ergonomic API - no need to create instances
     let id1 = banuid::generate();
@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use crate::IdGenerator;
+
+impl IdGenerator {
+    /// Time elapsed since `id` was created, by this generator's clock and layout.
+    ///
+    /// IDs from the future, e.g. minted on a node with a fast clock, have age zero.
+    pub fn age_of(&self, id: u64) -> Duration {
+        let created = Duration::from_millis(self.layout().extract_timestamp(id));
+        self.clock.now().saturating_sub(created)
+    }
+
+    /// Whether `id` was created no longer than `window` ago.
+    pub fn was_created_within(&self, id: u64, window: Duration) -> bool {
+        self.age_of(id) <= window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockClock;
+
+    #[test]
+    fn test_id_age() {
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 10_000);
+        let generator = IdGenerator::builder()
+            .time_source(clock.clone())
+            .shard_id(1)
+            .build()
+            .unwrap();
+
+        let id = generator.next_id();
+        clock.advance(1_500);
+        assert_eq!(generator.age_of(id), Duration::from_millis(1_500));
+        assert!(generator.was_created_within(id, Duration::from_secs(2)));
+        assert!(!generator.was_created_within(id, Duration::from_secs(1)));
+
+        clock.set(crate::CUSTOM_EPOCH);
+        assert_eq!(generator.age_of(id), Duration::ZERO);
+
+        let fresh = crate::generate();
+        assert!(crate::was_created_within(fresh, Duration::from_secs(5)));
+        assert!(crate::age_of(fresh) < Duration::from_secs(5));
+    }
+}
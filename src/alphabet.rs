@@ -0,0 +1,178 @@
+use std::fmt;
+
+use crate::id::ParseIdError;
+
+const INVALID: u8 = u8::MAX;
+
+/// A user-defined set of ASCII symbols to encode IDs with, e.g. one without
+/// vowels so encoded IDs never spell words.
+///
+/// Encoded IDs are zero-padded with the first symbol to a fixed width, so
+/// they sort like the IDs themselves when the symbols are in ASCII order.
+///
+/// ```
+/// use banuid::Alphabet;
+///
+/// let alphabet = Alphabet::new("0123456789bcdfghjkmnpqrstvwxyz").unwrap();
+/// let encoded = alphabet.encode(1234567890123456789);
+/// assert_eq!(encoded.len(), alphabet.encoded_len());
+/// assert_eq!(alphabet.decode(&encoded), Ok(1234567890123456789));
+/// assert!(Alphabet::new("abca").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: Vec<u8>,
+    // Digit value of each ASCII byte, INVALID if not in the alphabet
+    lookup: [u8; 128],
+    encoded_len: usize,
+}
+
+impl Alphabet {
+    /// Check that `symbols` holds at least 2 distinct printable ASCII characters.
+    pub fn new(symbols: &str) -> Result<Self, AlphabetError> {
+        let mut lookup = [INVALID; 128];
+        for (value, c) in symbols.chars().enumerate() {
+            if !c.is_ascii_graphic() {
+                return Err(AlphabetError::InvalidSymbol(c));
+            }
+            if lookup[c as usize] != INVALID {
+                return Err(AlphabetError::DuplicateSymbol(c));
+            }
+            lookup[c as usize] = value as u8;
+        }
+        let base = symbols.len() as u128;
+        if base < 2 {
+            return Err(AlphabetError::InvalidSize(symbols.len()));
+        }
+
+        // Smallest width whose capacity exceeds u64::MAX
+        let mut encoded_len = 1;
+        let mut capacity = base;
+        while capacity <= u64::MAX as u128 {
+            capacity *= base;
+            encoded_len += 1;
+        }
+
+        Ok(Alphabet {
+            symbols: symbols.as_bytes().to_vec(),
+            lookup,
+            encoded_len,
+        })
+    }
+
+    /// Number of symbols.
+    pub fn base(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Characters in every encoded ID.
+    pub fn encoded_len(&self) -> usize {
+        self.encoded_len
+    }
+
+    /// Encode `id` as exactly [`encoded_len`](Self::encoded_len) symbols.
+    pub fn encode(&self, id: u64) -> String {
+        let base = self.symbols.len() as u64;
+        let mut buf = vec![self.symbols[0]; self.encoded_len];
+        let mut value = id;
+        for slot in buf.iter_mut().rev() {
+            *slot = self.symbols[(value % base) as usize];
+            value /= base;
+        }
+        // Only ASCII from the alphabet was written
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Decode 1 to [`encoded_len`](Self::encoded_len) symbols, padded or not.
+    pub fn decode(&self, s: &str) -> Result<u64, ParseIdError> {
+        if s.is_empty() || s.len() > self.encoded_len {
+            return Err(ParseIdError::InvalidLength(s.len()));
+        }
+        let base = self.symbols.len() as u64;
+        s.chars().try_fold(0u64, |value, c| {
+            let digit = self
+                .lookup
+                .get(c as usize)
+                .copied()
+                .filter(|&d| d != INVALID)
+                .ok_or(ParseIdError::InvalidChar(c))?;
+            value
+                .checked_mul(base)
+                .and_then(|value| value.checked_add(digit as u64))
+                .ok_or(ParseIdError::Overflow)
+        })
+    }
+}
+
+/// Error returned by [`Alphabet::new`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AlphabetError {
+    /// Alphabets need at least 2 symbols.
+    InvalidSize(usize),
+    /// The symbol appears more than once.
+    DuplicateSymbol(char),
+    /// Symbols must be printable ASCII.
+    InvalidSymbol(char),
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlphabetError::InvalidSize(size) => {
+                write!(f, "alphabet has {} symbols, expected at least 2", size)
+            }
+            AlphabetError::DuplicateSymbol(c) => {
+                write!(f, "alphabet symbol {:?} appears more than once", c)
+            }
+            AlphabetError::InvalidSymbol(c) => {
+                write!(f, "alphabet symbol {:?} is not printable ASCII", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AlphabetError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alphabet() {
+        let binary = Alphabet::new("01").unwrap();
+        assert_eq!(binary.encoded_len(), 64);
+        assert_eq!(binary.decode(&"1".repeat(64)), Ok(u64::MAX));
+
+        let base62 =
+            Alphabet::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz")
+                .unwrap();
+        assert_eq!(
+            base62.encode(1234567890123456789),
+            crate::encode_base62(1234567890123456789)
+        );
+
+        let alphabet = Alphabet::new("bcdfghjkmnpqrstvwxz").unwrap();
+        for id in [0, 1, 18, 19, 1 << 40, u64::MAX] {
+            assert_eq!(alphabet.decode(&alphabet.encode(id)), Ok(id));
+        }
+        assert_eq!(alphabet.encode(0), "b".repeat(alphabet.encoded_len()));
+        assert_eq!(alphabet.decode("ba"), Err(ParseIdError::InvalidChar('a')));
+        assert_eq!(alphabet.decode("bé"), Err(ParseIdError::InvalidChar('é')));
+        assert_eq!(
+            alphabet.decode(&"z".repeat(alphabet.encoded_len())),
+            Err(ParseIdError::Overflow)
+        );
+
+        assert_eq!(Alphabet::new("a"), Err(AlphabetError::InvalidSize(1)));
+        assert_eq!(
+            Alphabet::new("aba"),
+            Err(AlphabetError::DuplicateSymbol('a'))
+        );
+        assert_eq!(
+            Alphabet::new("ab\n"),
+            Err(AlphabetError::InvalidSymbol('\n'))
+        );
+        assert_eq!(Alphabet::new("abé"), Err(AlphabetError::InvalidSymbol('é')));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::layout::Layout;
+use crate::varint::{decode_varint, encode_varint, VarintError};
+
+/// Avro `logicalType` name for banuid IDs.
+pub const AVRO_LOGICAL_TYPE: &str = "banuid";
+
+/// Append `id` to `out` as an Avro `long`, returning the number of bytes
+/// written.
+///
+/// Avro longs are signed, so IDs with the top bit set come out negative in
+/// readers that ignore the logical type; the bits are unchanged.
+///
+/// ```
+/// let mut out = Vec::new();
+/// assert_eq!(banuid::encode_avro_long(300, &mut out), 2);
+/// assert_eq!(out, [0xD8, 0x04]);
+/// assert_eq!(banuid::decode_avro_long(&out), Ok((300, 2)));
+/// ```
+pub fn encode_avro_long(id: u64, out: &mut Vec<u8>) -> usize {
+    let value = id as i64;
+    encode_varint(((value << 1) ^ (value >> 63)) as u64, out)
+}
+
+/// Read an Avro `long` written by [`encode_avro_long`] from the start of
+/// `bytes`, returning the ID with the number of bytes consumed.
+pub fn decode_avro_long(bytes: &[u8]) -> Result<(u64, usize), VarintError> {
+    let (zigzag, len) = decode_varint(bytes)?;
+    Ok(((zigzag >> 1) ^ (zigzag & 1).wrapping_neg(), len))
+}
+
+impl Layout {
+    /// Avro schema for an ID field of this layout: a `long` with the
+    /// [`AVRO_LOGICAL_TYPE`] logical type.
+    ///
+    /// The layout is recorded as extra attributes so consumers registered
+    /// with a schema registry can decode IDs without sharing code. Readers
+    /// that don't know the logical type fall back to a plain `long`.
+    ///
+    /// ```
+    /// assert_eq!(
+    ///     banuid::Layout::SNOWFLAKE.avro_schema(),
+    ///     r#"{"type":"long","logicalType":"banuid","epoch":1288834974657,"timestamp_bits":41,"shard_bits":10,"sequence_bits":12,"tick_micros":1000}"#
+    /// );
+    /// ```
+    pub fn avro_schema(&self) -> String {
+        format!(
+            r#"{{"type":"long","logicalType":"{}","epoch":{},"timestamp_bits":{},"shard_bits":{},"sequence_bits":{},"tick_micros":{}}}"#,
+            AVRO_LOGICAL_TYPE,
+            self.epoch(),
+            self.timestamp_bits(),
+            self.shard_bits(),
+            self.sequence_bits(),
+            self.unit().as_micros()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avro_long() {
+        // Reference encodings from the Avro specification
+        for (value, expected) in [
+            (0i64, &[0x00][..]),
+            (-1, &[0x01]),
+            (1, &[0x02]),
+            (-64, &[0x7F]),
+        ] {
+            let mut out = Vec::new();
+            encode_avro_long(value as u64, &mut out);
+            assert_eq!(out, expected);
+        }
+
+        for id in [1u64 << 62, 1 << 63, u64::MAX] {
+            let mut out = Vec::new();
+            let len = encode_avro_long(id, &mut out);
+            assert_eq!(decode_avro_long(&out), Ok((id, len)));
+        }
+        assert_eq!(decode_avro_long(&[0x80]), Err(VarintError::Truncated));
+    }
+
+    #[test]
+    fn test_avro_schema() {
+        let schema = Layout::DEFAULT.avro_schema();
+        assert!(schema.starts_with(r#"{"type":"long","logicalType":"banuid","#));
+        assert!(schema.contains(&format!(r#""epoch":{}"#, crate::CUSTOM_EPOCH)));
+    }
+}
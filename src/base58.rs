@@ -0,0 +1,89 @@
+use crate::id::{Id, ParseIdError};
+
+/// Bitcoin's alphabet: base62 without `0`, `O`, `I` and `l`. Still in ASCII
+/// order, so fixed-width strings sort like the numbers they encode.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Characters needed for any `u64`.
+const BASE58_LEN: usize = 11;
+
+/// Encode `id` as 11 Base58 characters in the Bitcoin alphabet, padded with
+/// `1` (the zero digit) so that encoded IDs sort like the IDs themselves.
+///
+/// ```
+/// let encoded = banuid::encode_base58(1234567890123456789);
+/// assert_eq!(encoded, "3sDK21t5nHJ");
+/// assert_eq!(banuid::decode_base58(&encoded), Ok(1234567890123456789));
+/// assert_eq!(banuid::decode_base58("2"), Ok(1));
+/// ```
+pub fn encode_base58(id: u64) -> String {
+    let mut buf = [0u8; BASE58_LEN];
+    encode_base58_into(id, &mut buf);
+    // Only ASCII from the alphabet was written
+    String::from_utf8(buf.to_vec()).unwrap()
+}
+
+/// Write [`encode_base58`]'s 11 characters to the start of `buf` without
+/// allocating, returning the number of bytes written.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than 11 bytes.
+pub fn encode_base58_into(id: u64, buf: &mut [u8]) -> usize {
+    let mut value = id;
+    for slot in buf[..BASE58_LEN].iter_mut().rev() {
+        *slot = BASE58_ALPHABET[(value % 58) as usize];
+        value /= 58;
+    }
+    BASE58_LEN
+}
+
+/// Decode a Base58 ID of 1 to 11 characters, padded or not.
+pub fn decode_base58(s: &str) -> Result<u64, ParseIdError> {
+    if s.is_empty() || s.len() > BASE58_LEN {
+        return Err(ParseIdError::InvalidLength(s.len()));
+    }
+    s.chars().try_fold(0u64, |value, c| {
+        let digit = BASE58_ALPHABET
+            .iter()
+            .position(|&d| d as char == c)
+            .ok_or(ParseIdError::InvalidChar(c))?;
+        value
+            .checked_mul(58)
+            .and_then(|value| value.checked_add(digit as u64))
+            .ok_or(ParseIdError::Overflow)
+    })
+}
+
+impl<T> Id<T> {
+    /// The ID as 11 Base58 characters, see [`encode_base58`].
+    pub fn to_base58(&self) -> String {
+        encode_base58(self.as_u64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58_round_trip() {
+        let ids = [0, 1, 57, 58, 1 << 40, u64::MAX - 1, u64::MAX];
+        for pair in ids.windows(2) {
+            assert!(encode_base58(pair[0]) < encode_base58(pair[1]));
+        }
+        for id in ids {
+            assert_eq!(decode_base58(&encode_base58(id)), Ok(id));
+        }
+        assert_eq!(encode_base58(0), "11111111111");
+        assert_eq!(decode_base58("0"), Err(ParseIdError::InvalidChar('0')));
+        assert_eq!(decode_base58("zzzzzzzzzzz"), Err(ParseIdError::Overflow));
+        assert_eq!(
+            decode_base58("111111111112"),
+            Err(ParseIdError::InvalidLength(12))
+        );
+
+        let id: Id = Id::from_u64(58).unwrap();
+        assert_eq!(id.to_base58(), "11111111121");
+    }
+}
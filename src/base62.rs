@@ -0,0 +1,108 @@
+use crate::id::{Id, ParseIdError};
+
+/// Digits in ASCII order, so fixed-width strings sort like the numbers they encode.
+const BASE62_ALPHABET: &[u8; 62] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Characters needed for any `u64`.
+pub(crate) const BASE62_LEN: usize = 11;
+
+/// Encode `id` as 11 base62 characters (`0-9A-Za-z`), zero-padded so that
+/// encoded IDs sort in the same order as the IDs themselves.
+///
+/// ```
+/// let encoded = banuid::encode_base62(1234567890123456789);
+/// assert_eq!(encoded, "1TCKi1nFuNh");
+/// assert_eq!(banuid::encode_base62(61), "0000000000z");
+/// assert_eq!(banuid::decode_base62(&encoded), Ok(1234567890123456789));
+/// ```
+pub fn encode_base62(id: u64) -> String {
+    let mut buf = [0u8; BASE62_LEN];
+    encode_base62_into(id, &mut buf);
+    // Only ASCII from the alphabet was written
+    String::from_utf8(buf.to_vec()).unwrap()
+}
+
+/// Write [`encode_base62`]'s 11 characters to the start of `buf` without
+/// allocating, returning the number of bytes written.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than 11 bytes.
+pub fn encode_base62_into(id: u64, buf: &mut [u8]) -> usize {
+    let mut value = id;
+    for slot in buf[..BASE62_LEN].iter_mut().rev() {
+        *slot = BASE62_ALPHABET[(value % 62) as usize];
+        value /= 62;
+    }
+    BASE62_LEN
+}
+
+/// Decode a base62 ID of 1 to 11 characters, padded or not.
+pub fn decode_base62(s: &str) -> Result<u64, ParseIdError> {
+    if s.is_empty() || s.len() > BASE62_LEN {
+        return Err(ParseIdError::InvalidLength(s.len()));
+    }
+    decode(s)
+}
+
+impl<T> Id<T> {
+    /// The ID as 11 base62 characters, see [`encode_base62`].
+    pub fn to_base62(&self) -> String {
+        encode_base62(self.as_u64())
+    }
+}
+
+// Decode base62 digits of any length, failing past u64::MAX
+pub(crate) fn decode(s: &str) -> Result<u64, ParseIdError> {
+    s.chars().try_fold(0u64, |value, c| {
+        let digit = BASE62_ALPHABET
+            .iter()
+            .position(|&d| d as char == c)
+            .ok_or(ParseIdError::InvalidChar(c))?;
+        value
+            .checked_mul(62)
+            .and_then(|value| value.checked_add(digit as u64))
+            .ok_or(ParseIdError::Overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base62_decode() {
+        assert_eq!(decode("0"), Ok(0));
+        assert_eq!(decode("z"), Ok(61));
+        assert_eq!(decode("10"), Ok(62));
+        assert_eq!(decode("LygHa16AHYF"), Ok(u64::MAX));
+        assert_eq!(decode("LygHa16AHYG"), Err(ParseIdError::Overflow));
+        assert_eq!(decode("ab-c"), Err(ParseIdError::InvalidChar('-')));
+    }
+
+    #[test]
+    fn test_base62_round_trip_preserves_order() {
+        let ids = [0, 1, 61, 62, 1 << 40, u64::MAX - 1, u64::MAX];
+        for pair in ids.windows(2) {
+            assert!(encode_base62(pair[0]) < encode_base62(pair[1]));
+        }
+        for id in ids {
+            assert_eq!(encode_base62(id).len(), BASE62_LEN);
+            assert_eq!(decode_base62(&encode_base62(id)), Ok(id));
+        }
+        assert_eq!(decode_base62(""), Err(ParseIdError::InvalidLength(0)));
+        assert_eq!(
+            decode_base62("000000000001"),
+            Err(ParseIdError::InvalidLength(12))
+        );
+
+        let mut buf = [0u8; 64];
+        let len = encode_base62_into(u64::MAX, &mut buf);
+        assert_eq!(&buf[..len], b"LygHa16AHYF");
+
+        let id: Id = Id::from_u64(1234567890123456789).unwrap();
+        assert_eq!(id.to_base62(), "1TCKi1nFuNh");
+        assert_eq!(id.to_base62().parse(), Ok(id));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::id::{Id, ParseIdError};
+
+/// RFC 4648 URL-safe alphabet.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Characters needed for any `u64`.
+const BASE64_LEN: usize = 11;
+
+/// Encode `id` as 11 URL-safe Base64 characters (`A-Za-z0-9-_`), safe to put
+/// in URLs, query strings and tokens without percent-encoding.
+///
+/// This is the unpadded base64url encoding of the ID's big-endian bytes, so
+/// any standard decoder reads it back as those 8 bytes.
+///
+/// ```
+/// let encoded = banuid::encode_base64(1234567890123456789);
+/// assert_eq!(encoded, "ESIQ9H3pgRU");
+/// assert_eq!(banuid::decode_base64(&encoded), Ok(1234567890123456789));
+/// ```
+pub fn encode_base64(id: u64) -> String {
+    let mut buf = [0u8; BASE64_LEN];
+    encode_base64_into(id, &mut buf);
+    // Only ASCII from the alphabet was written
+    String::from_utf8(buf.to_vec()).unwrap()
+}
+
+/// Write [`encode_base64`]'s 11 characters to the start of `buf` without
+/// allocating, returning the number of bytes written.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than 11 bytes.
+pub fn encode_base64_into(id: u64, buf: &mut [u8]) -> usize {
+    // 11 characters hold 66 bits, the last two are always zero
+    let mut value = (id as u128) << 2;
+    for slot in buf[..BASE64_LEN].iter_mut().rev() {
+        *slot = BASE64_ALPHABET[(value & 0x3F) as usize];
+        value >>= 6;
+    }
+    BASE64_LEN
+}
+
+/// Decode an ID written by [`encode_base64`].
+pub fn decode_base64(s: &str) -> Result<u64, ParseIdError> {
+    if s.len() != BASE64_LEN {
+        return Err(ParseIdError::InvalidLength(s.len()));
+    }
+    let value = s.chars().try_fold(0u128, |value, c| {
+        let digit = BASE64_ALPHABET
+            .iter()
+            .position(|&d| d as char == c)
+            .ok_or(ParseIdError::InvalidChar(c))?;
+        Ok((value << 6) | digit as u128)
+    })?;
+    // Reject the trailing characters that would set the two padding bits
+    if value & 0b11 != 0 {
+        return Err(ParseIdError::InvalidChar(s.chars().last().unwrap()));
+    }
+    Ok((value >> 2) as u64)
+}
+
+impl<T> Id<T> {
+    /// The ID as 11 URL-safe Base64 characters, see [`encode_base64`].
+    pub fn to_base64(&self) -> String {
+        encode_base64(self.as_u64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_round_trip() {
+        for id in [0, 1, 63, 64, 1 << 40, u64::MAX - 1, u64::MAX] {
+            assert_eq!(decode_base64(&encode_base64(id)), Ok(id));
+        }
+        assert_eq!(encode_base64(0), "AAAAAAAAAAA");
+        assert_eq!(encode_base64(u64::MAX), "__________8");
+        assert_eq!(
+            decode_base64("__________-"),
+            Err(ParseIdError::InvalidChar('-'))
+        );
+        assert_eq!(
+            decode_base64("AAAAAAAAAA+"),
+            Err(ParseIdError::InvalidChar('+'))
+        );
+        assert_eq!(decode_base64("AAAA"), Err(ParseIdError::InvalidLength(4)));
+
+        let id: Id = Id::from_u64(1234567890123456789).unwrap();
+        assert_eq!(id.to_base64(), "ESIQ9H3pgRU");
+    }
+}
@@ -0,0 +1,45 @@
+use std::io;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::id::{Id, ParseIdError};
+
+/// Serializes as a little-endian `u64`, whatever the marker type.
+impl<T> BorshSerialize for Id<T> {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.as_u64().serialize(writer)
+    }
+}
+
+impl<T> BorshDeserialize for Id<T> {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let value = u64::deserialize_reader(reader)?;
+        Id::from_u64(value)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, ParseIdError::Zero))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IdComponents, Layout};
+
+    #[test]
+    fn test_borsh_round_trip() {
+        struct Order;
+
+        let id: Id<Order> = Id::from_u64(1 << 60).unwrap();
+        let bytes = borsh::to_vec(&id).unwrap();
+        assert_eq!(bytes, (1u64 << 60).to_le_bytes());
+        assert_eq!(borsh::from_slice::<Id<Order>>(&bytes).unwrap(), id);
+        assert!(borsh::from_slice::<Id>(&[0; 8]).is_err());
+
+        let components = Layout::DEFAULT.decode(Layout::DEFAULT.encode(1_000, 7, 3));
+        let bytes = borsh::to_vec(&components).unwrap();
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(
+            borsh::from_slice::<IdComponents>(&bytes).unwrap(),
+            components
+        );
+    }
+}
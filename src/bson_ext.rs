@@ -0,0 +1,81 @@
+use std::fmt;
+
+use bson::spec::ElementType;
+use bson::Bson;
+
+use crate::id::Id;
+
+/// Stored as `Int64` via [`Id::as_i64`], keeping all 64 bits. IDs with the
+/// top bit set come out negative, so use an
+/// [`i64`-safe](crate::Layout::new_i64_safe) layout if MongoDB must sort or
+/// range-query them.
+impl<T> From<Id<T>> for Bson {
+    fn from(id: Id<T>) -> Self {
+        Bson::Int64(id.as_i64())
+    }
+}
+
+/// Accepts `Int64`, and `Int32` for small IDs written by other drivers.
+impl<T> TryFrom<Bson> for Id<T> {
+    type Error = FromBsonError;
+
+    fn try_from(value: Bson) -> Result<Self, Self::Error> {
+        let value = match value {
+            Bson::Int64(value) => value,
+            Bson::Int32(value) => value as i64,
+            other => return Err(FromBsonError::UnexpectedType(other.element_type())),
+        };
+        Id::from_i64(value).ok_or(FromBsonError::Zero)
+    }
+}
+
+/// Error converting a [`Bson`] value to an [`Id`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FromBsonError {
+    /// The value is not an integer.
+    UnexpectedType(ElementType),
+    /// The value is zero, which is never a valid ID.
+    Zero,
+}
+
+impl fmt::Display for FromBsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromBsonError::UnexpectedType(ty) => {
+                write!(f, "expected a BSON integer ID, found {:?}", ty)
+            }
+            FromBsonError::Zero => write!(f, "ID cannot be zero"),
+        }
+    }
+}
+
+impl std::error::Error for FromBsonError {}
+
+#[cfg(test)]
+mod tests {
+    use bson::doc;
+
+    use super::*;
+
+    #[test]
+    fn test_bson_round_trip() {
+        struct Order;
+
+        let id: Id<Order> = Id::from_u64(u64::MAX).unwrap();
+        let document = doc! { "_id": id };
+        assert_eq!(document.get_i64("_id"), Ok(-1));
+        let value = document.get("_id").cloned().unwrap();
+        assert_eq!(Id::<Order>::try_from(value), Ok(id));
+
+        assert_eq!(Id::<Order>::try_from(Bson::Int32(7)).unwrap().as_u64(), 7);
+        assert_eq!(
+            Id::<Order>::try_from(Bson::Int64(0)),
+            Err(FromBsonError::Zero)
+        );
+        assert_eq!(
+            Id::<Order>::try_from(Bson::String("7".into())),
+            Err(FromBsonError::UnexpectedType(ElementType::String))
+        );
+    }
+}
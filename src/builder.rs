@@ -0,0 +1,845 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::clock::{ClockEvent, ClockHook, ForwardStepPolicy, SystemClock, TimeSource};
+use crate::layout::{FieldOrder, Layout, Rounding, TimeUnit};
+use crate::persist::{FileStateStore, StateStore};
+use crate::sequence::SequenceMode;
+use crate::IdGenerator;
+
+/// Configures an [`IdGenerator`] with a custom bit layout.
+///
+/// ```
+/// use banuid::IdGenerator;
+///
+/// // Trade shard space for a higher per-millisecond sequence
+/// let generator = IdGenerator::builder()
+///     .timestamp_bits(41)
+///     .shard_bits(8)
+///     .sequence_bits(15)
+///     .shard_id(7)
+///     .build()
+///     .unwrap();
+///
+/// let id = generator.next_id();
+/// assert_eq!(generator.layout().extract_shard_id(id), 7);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IdGeneratorBuilder {
+    epoch: SystemTime,
+    unit: TimeUnit,
+    rounding: Rounding,
+    order: FieldOrder,
+    js_safe: bool,
+    i64_safe: bool,
+    timestamp_bits: Option<u8>,
+    shard_bits: Option<u8>,
+    sequence_bits: Option<u8>,
+    datacenter_bits: u8,
+    sequence_mode: SequenceMode,
+    max_spillover: u64,
+    strict_monotonic: bool,
+    rollback_tolerance: Duration,
+    time_source: Arc<dyn TimeSource>,
+    state_store: Option<Arc<dyn StateStore>>,
+    state_lease: Duration,
+    min_plausible_time: Option<SystemTime>,
+    clock_hook: Option<ClockHook>,
+    forward_jump_threshold: Duration,
+    forward_step_policy: ForwardStepPolicy,
+    leap_second_hold: bool,
+    version: Option<(u8, u8)>,
+    expiry_class_bits: u8,
+    checksum_bits: u8,
+    shard_id: Option<u16>,
+    datacenter_id: Option<u16>,
+    worker_id: Option<u16>,
+}
+
+impl IdGeneratorBuilder {
+    pub fn new() -> Self {
+        let layout = Layout::DEFAULT;
+        IdGeneratorBuilder {
+            epoch: UNIX_EPOCH + Duration::from_millis(layout.epoch()),
+            unit: layout.unit(),
+            rounding: layout.rounding(),
+            order: layout.field_order(),
+            js_safe: false,
+            i64_safe: false,
+            timestamp_bits: None,
+            shard_bits: None,
+            sequence_bits: None,
+            datacenter_bits: 0,
+            sequence_mode: SequenceMode::Counter,
+            max_spillover: 0,
+            strict_monotonic: false,
+            rollback_tolerance: Duration::ZERO,
+            time_source: Arc::new(SystemClock),
+            state_store: None,
+            state_lease: Duration::from_secs(1),
+            min_plausible_time: None,
+            clock_hook: None,
+            forward_jump_threshold: Duration::from_secs(1),
+            forward_step_policy: ForwardStepPolicy::Accept,
+            leap_second_hold: false,
+            version: None,
+            expiry_class_bits: 0,
+            checksum_bits: 0,
+            shard_id: None,
+            datacenter_id: None,
+            worker_id: None,
+        }
+    }
+
+    /// Measure timestamps from `epoch` instead of 2024-01-01.
+    pub fn epoch(mut self, epoch: SystemTime) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Measure timestamps from `epoch` milliseconds since the Unix epoch.
+    pub fn epoch_millis(mut self, epoch: u64) -> Self {
+        self.epoch = UNIX_EPOCH + Duration::from_millis(epoch);
+        self
+    }
+
+    /// Store timestamps in `unit` ticks instead of milliseconds.
+    pub fn unit(mut self, unit: TimeUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Round the current time to the nearest tick instead of truncating it.
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Place the sequence above or below the shard field.
+    pub fn field_order(mut self, order: FieldOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Keep IDs within 53 bits so JavaScript consumers read them exactly.
+    ///
+    /// Field widths default to [`Layout::JS_SAFE`] and must total at most 53 bits.
+    pub fn js_safe(mut self) -> Self {
+        self.js_safe = true;
+        self
+    }
+
+    /// Keep the top bit clear so IDs stay positive in signed BIGINT columns.
+    ///
+    /// Field widths default to [`Layout::I64_SAFE`] and must total at most 63 bits.
+    pub fn i64_safe(mut self) -> Self {
+        self.i64_safe = true;
+        self
+    }
+
+    pub fn timestamp_bits(mut self, bits: u8) -> Self {
+        self.timestamp_bits = Some(bits);
+        self
+    }
+
+    pub fn shard_bits(mut self, bits: u8) -> Self {
+        self.shard_bits = Some(bits);
+        self
+    }
+
+    pub fn sequence_bits(mut self, bits: u8) -> Self {
+        self.sequence_bits = Some(bits);
+        self
+    }
+
+    /// Reserve the top `bits` of the shard field for a datacenter ID.
+    pub fn datacenter_bits(mut self, bits: u8) -> Self {
+        self.datacenter_bits = bits;
+        self
+    }
+
+    /// Reserve `bits` version bits (taken from the timestamp field) and stamp `version` on every ID.
+    pub fn version(mut self, bits: u8, version: u8) -> Self {
+        self.version = Some((bits, version));
+        self
+    }
+
+    /// Reserve `bits` for a per-ID expiry class (taken from the shard field),
+    /// see [`IdGenerator::next_id_with_expiry_class`].
+    pub fn expiry_class_bits(mut self, bits: u8) -> Self {
+        self.expiry_class_bits = bits;
+        self
+    }
+
+    /// Reserve `bits` checksum bits (taken from the sequence field), see [`Layout::with_checksum`].
+    pub fn checksum_bits(mut self, bits: u8) -> Self {
+        self.checksum_bits = bits;
+        self
+    }
+
+    /// Choose how the sequence field is filled.
+    pub fn sequence_mode(mut self, mode: SequenceMode) -> Self {
+        self.sequence_mode = mode;
+        self
+    }
+
+    /// Let bursts borrow up to `ticks` future ticks instead of waiting once
+    /// a tick's sequence is exhausted.
+    ///
+    /// IDs stay unique and increasing, but their timestamps may run up to
+    /// `ticks` ahead of the clock while a burst lasts. Defaults to 0 (always wait).
+    pub fn max_spillover(mut self, ticks: u64) -> Self {
+        self.max_spillover = ticks;
+        self
+    }
+
+    /// Run the timestamp as a hybrid logical clock: it advances to
+    /// `max(wall clock, last timestamp)`, borrowing future ticks without limit
+    /// when a tick's sequence runs out and never failing on clock regressions.
+    ///
+    /// Feed IDs received from other nodes to [`IdGenerator::observe`] so later
+    /// local IDs sort after them despite modest clock skew.
+    pub fn hybrid_logical_clock(mut self) -> Self {
+        self.max_spillover = u64::MAX;
+        self
+    }
+
+    /// Guarantee that every ID is greater than the one before it, even across
+    /// clock regressions, by advancing a logical timestamp while the wall
+    /// clock lags, like [`hybrid_logical_clock`](Self::hybrid_logical_clock).
+    ///
+    /// Requires a counting [`SequenceMode`]. The guarantee covers one process;
+    /// without a [state store](Self::state_store), a restart during a clock
+    /// regression can issue IDs below ones issued before it. IDs with
+    /// different expiry classes from the same tick are not ordered by issue
+    /// time.
+    pub fn strict_monotonic(mut self) -> Self {
+        self.strict_monotonic = true;
+        self
+    }
+
+    /// Wait out clock regressions up to `tolerance` in
+    /// [`IdGenerator::try_next_id`] instead of failing; larger ones still error.
+    ///
+    /// Defaults to zero, so any regression is reported.
+    pub fn rollback_tolerance(mut self, tolerance: Duration) -> Self {
+        self.rollback_tolerance = tolerance;
+        self
+    }
+
+    /// Read timestamps from `source` instead of the system clock.
+    pub fn time_source(mut self, source: impl TimeSource + 'static) -> Self {
+        self.time_source = Arc::new(source);
+        self
+    }
+
+    /// Refuse to build while the clock reads earlier than `floor` or the
+    /// layout's epoch, e.g. 1970 on an embedded board that has not synced its clock yet.
+    pub fn min_plausible_time(mut self, floor: SystemTime) -> Self {
+        self.min_plausible_time = Some(floor);
+        self
+    }
+
+    /// Refuse to build while the clock reads earlier than this crate's release
+    /// year (2025) or the layout's epoch, whichever is later.
+    pub fn check_clock(self) -> Self {
+        self.min_plausible_time(UNIX_EPOCH + Duration::from_millis(CLOCK_FLOOR))
+    }
+
+    /// Call `hook` on clock anomalies: backwards and large forward jumps, and
+    /// exhausted sequences.
+    ///
+    /// The hook runs on the generating thread without the generator locked.
+    pub fn on_clock_event(mut self, hook: impl Fn(ClockEvent) + Send + Sync + 'static) -> Self {
+        self.clock_hook = Some(ClockHook(Arc::new(hook)));
+        self
+    }
+
+    /// Report [`ClockEvent::JumpedForward`] when the wall clock gains more than
+    /// `threshold` on the monotonic clock between two IDs. Defaults to one second.
+    pub fn forward_jump_threshold(mut self, threshold: Duration) -> Self {
+        self.forward_jump_threshold = threshold;
+        self
+    }
+
+    /// Choose how forward steps beyond the
+    /// [forward jump threshold](Self::forward_jump_threshold) are handled.
+    pub fn forward_step_policy(mut self, policy: ForwardStepPolicy) -> Self {
+        self.forward_step_policy = policy;
+        self
+    }
+
+    /// Ride out clock regressions of up to one second, as when a leap second
+    /// repeats, by holding the last timestamp and borrowing following ticks
+    /// until the clock catches up, instead of waiting or failing.
+    ///
+    /// Each such regression is reported once as [`ClockEvent::LeapSecond`].
+    pub fn leap_second_hold(mut self) -> Self {
+        self.leap_second_hold = true;
+        self
+    }
+
+    /// Record a lease on upcoming timestamps in `store` and resume past it on
+    /// the next build, so a restarted process cannot reissue IDs for a tick it
+    /// already used.
+    ///
+    /// A new lease is [reserved](StateStore::reserve) only when IDs reach the
+    /// end of the current one, see [`state_lease`](Self::state_lease). After a restart, IDs wait for the clock to
+    /// pass the saved lease, and a clock that moved back further is reported
+    /// like any other regression. The saved value is only meaningful to
+    /// generators with the same layout.
+    pub fn state_store(mut self, store: impl StateStore + 'static) -> Self {
+        self.state_store = Some(Arc::new(store));
+        self
+    }
+
+    /// How far ahead of the current timestamp each
+    /// [state store](Self::state_store) write reserves. Defaults to one second.
+    ///
+    /// Longer leases mean fewer writes but a longer wait after a quick
+    /// restart; zero writes on every tick in which IDs are issued.
+    pub fn state_lease(mut self, lease: Duration) -> Self {
+        self.state_lease = lease;
+        self
+    }
+
+    /// Shorthand for [`state_store`](Self::state_store) with a [`FileStateStore`] at `path`.
+    pub fn state_file(self, path: impl Into<PathBuf>) -> Self {
+        self.state_store(FileStateStore::new(path))
+    }
+
+    /// Use a fixed shard ID instead of deriving one from the environment.
+    pub fn shard_id(mut self, shard_id: u16) -> Self {
+        self.shard_id = Some(shard_id);
+        self
+    }
+
+    /// Set the datacenter part of the shard ID; requires [`datacenter_bits`](Self::datacenter_bits).
+    pub fn datacenter_id(mut self, datacenter_id: u16) -> Self {
+        self.datacenter_id = Some(datacenter_id);
+        self
+    }
+
+    /// Set the worker part of the shard ID.
+    pub fn worker_id(mut self, worker_id: u16) -> Self {
+        self.worker_id = Some(worker_id);
+        self
+    }
+
+    pub fn build(self) -> Result<IdGenerator, BuildError> {
+        let epoch = self
+            .epoch
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| BuildError::EpochBeforeUnixEpoch)?;
+        let base = if self.js_safe {
+            Layout::JS_SAFE
+        } else if self.i64_safe {
+            Layout::I64_SAFE
+        } else {
+            Layout::DEFAULT
+        };
+        let timestamp_bits = self.timestamp_bits.unwrap_or(base.timestamp_bits());
+        let shard_bits = self.shard_bits.unwrap_or(base.shard_bits());
+        let sequence_bits = self.sequence_bits.unwrap_or(base.sequence_bits());
+
+        let layout = if self.js_safe {
+            Layout::new_js_safe(timestamp_bits, shard_bits, sequence_bits)?
+        } else if self.i64_safe {
+            Layout::new_i64_safe(timestamp_bits, shard_bits, sequence_bits)?
+        } else {
+            Layout::new(timestamp_bits, shard_bits, sequence_bits)?
+        };
+        let layout = layout
+            .with_epoch(epoch.as_millis() as u64)
+            .with_unit(self.unit)
+            .with_rounding(self.rounding)
+            .with_field_order(self.order)
+            .split_shard(self.datacenter_bits)?
+            .with_expiry_class(self.expiry_class_bits)?
+            .with_checksum(self.checksum_bits)?;
+        let layout = match self.version {
+            Some((bits, version)) => layout.with_version(bits, version)?,
+            None => layout,
+        };
+
+        let node_id = match (self.datacenter_id, self.worker_id) {
+            (None, None) => None,
+            _ if self.shard_id.is_some() => return Err(BuildError::ConflictingShardId),
+            (datacenter_id, worker_id) => {
+                let datacenter_id = datacenter_id.unwrap_or(0);
+                let worker_id = worker_id.unwrap_or(0);
+                check_range("datacenter", datacenter_id, layout.max_datacenter_id())?;
+                check_range("worker", worker_id, layout.max_worker_id())?;
+                Some(layout.shard_id_from(datacenter_id, worker_id))
+            }
+        };
+
+        let shard_id = match self.shard_id.or(node_id) {
+            Some(shard_id) if shard_id as u64 > layout.max_shard_id() => {
+                return Err(BuildError::ShardIdOutOfRange {
+                    shard_id,
+                    max: layout.max_shard_id() as u16,
+                });
+            }
+            Some(shard_id) => shard_id,
+            None => (crate::derive_shard_id() as u64 & layout.max_shard_id()) as u16,
+        };
+
+        if let Some(floor) = self.min_plausible_time {
+            let floor_ms = floor
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |floor| floor.as_millis() as u64)
+                .max(layout.epoch());
+            let now_ms = self.time_source.now().as_millis() as u64;
+            if now_ms < floor_ms {
+                return Err(BuildError::ImplausibleClock { now_ms, floor_ms });
+            }
+        }
+
+        if let SequenceMode::RandomLowBits(bits) = self.sequence_mode {
+            // Keep at least one counter bit
+            let max = layout.sequence_bits().saturating_sub(1);
+            if bits > max {
+                return Err(BuildError::FieldTooWide {
+                    field: "random",
+                    bits,
+                    max,
+                });
+            }
+        }
+
+        let max_spillover = if self.strict_monotonic {
+            if self.sequence_mode == SequenceMode::Random {
+                return Err(BuildError::RandomSequenceNotMonotonic);
+            }
+            u64::MAX
+        } else {
+            self.max_spillover
+        };
+        if cfg!(not(feature = "getrandom"))
+            && matches!(
+                self.sequence_mode,
+                SequenceMode::Random | SequenceMode::RandomLowBits(_)
+            )
+        {
+            return Err(BuildError::EntropyUnavailable);
+        }
+
+        let mut generator = IdGenerator::from_parts(shard_id, layout);
+        generator.sequence_mode = self.sequence_mode;
+        generator.max_spillover = max_spillover;
+        generator.rollback_tolerance = self.rollback_tolerance;
+        generator.clock = self.time_source;
+        generator.clock_hook = self.clock_hook;
+        generator.forward_jump_threshold = self.forward_jump_threshold;
+        generator.forward_step_policy = self.forward_step_policy;
+        generator.leap_second_hold = self.leap_second_hold;
+        if let Some(store) = self.state_store {
+            let saved = store
+                .load()
+                .map_err(|err| BuildError::StateUnreadable(err.kind()))?;
+            if let Some(timestamp) = saved {
+                generator.resume_from(timestamp);
+            }
+            generator.state_store = Some(store);
+            generator.state_lease =
+                (self.state_lease.as_micros() / layout.unit().as_micros() as u128) as u64;
+        }
+        Ok(generator)
+    }
+}
+
+/// Earliest plausible wall clock reading, 2025-01-01 00:00:00 UTC
+const CLOCK_FLOOR: u64 = 1735689600000;
+
+fn check_range(field: &'static str, value: u16, max: u64) -> Result<(), BuildError> {
+    if value as u64 > max {
+        return Err(BuildError::NodeIdOutOfRange {
+            field,
+            value,
+            max: max as u16,
+        });
+    }
+    Ok(())
+}
+
+impl Default for IdGeneratorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Errors returned when an [`IdGeneratorBuilder`] configuration is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// Timestamp, shard and sequence widths must add up to 63 or 64 bits.
+    InvalidTotalBits { total: u32 },
+    /// A field is wider than its extracted value type can hold.
+    FieldTooWide {
+        field: &'static str,
+        bits: u8,
+        max: u8,
+    },
+    /// The requested shard ID does not fit the layout's shard field.
+    ShardIdOutOfRange { shard_id: u16, max: u16 },
+    /// A datacenter or worker ID does not fit its part of the shard field.
+    NodeIdOutOfRange {
+        field: &'static str,
+        value: u16,
+        max: u16,
+    },
+    /// A shard ID was given together with a datacenter or worker ID.
+    ConflictingShardId,
+    /// [Strict monotonic](IdGeneratorBuilder::strict_monotonic) generation was
+    /// combined with [`SequenceMode::Random`].
+    RandomSequenceNotMonotonic,
+    /// The version does not fit the reserved version bits.
+    VersionOutOfRange { version: u8, max: u8 },
+    /// A versioned layout reserves a different number of version bits than expected.
+    VersionBitsMismatch { expected: u8, found: u8 },
+    /// A JavaScript-safe layout uses more than 53 bits.
+    NotJsSafe { total: u32 },
+    /// An i64-safe layout uses all 64 bits.
+    NotI64Safe { total: u32 },
+    /// A short ID layout uses more than 32 bits.
+    NotU32Safe { total: u32 },
+    /// The custom epoch is earlier than 1970-01-01.
+    EpochBeforeUnixEpoch,
+    /// The clock reads earlier than the plausibility floor, see
+    /// [`IdGeneratorBuilder::min_plausible_time`].
+    ImplausibleClock { now_ms: u64, floor_ms: u64 },
+    /// Saved generator state could not be read.
+    StateUnreadable(std::io::ErrorKind),
+    /// [`init`](crate::init) was called after the default generator was set up.
+    AlreadyInitialized,
+    /// [`SequenceMode::Random`] or [`SequenceMode::RandomLowBits`] was chosen
+    /// without the `getrandom` feature.
+    EntropyUnavailable,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidTotalBits { total } => {
+                write!(f, "layout uses {} bits, expected 63 or 64", total)
+            }
+            BuildError::FieldTooWide { field, bits, max } => {
+                write!(
+                    f,
+                    "{} field is {} bits wide, at most {} allowed",
+                    field, bits, max
+                )
+            }
+            BuildError::ShardIdOutOfRange { shard_id, max } => {
+                write!(
+                    f,
+                    "shard ID {} exceeds the layout maximum of {}",
+                    shard_id, max
+                )
+            }
+            BuildError::NodeIdOutOfRange { field, value, max } => {
+                write!(
+                    f,
+                    "{} ID {} exceeds the layout maximum of {}",
+                    field, value, max
+                )
+            }
+            BuildError::ConflictingShardId => {
+                write!(
+                    f,
+                    "set either a shard ID or datacenter/worker IDs, not both"
+                )
+            }
+            BuildError::RandomSequenceNotMonotonic => {
+                write!(f, "random sequences cannot be strictly monotonic")
+            }
+            BuildError::VersionOutOfRange { version, max } => {
+                write!(f, "version {} exceeds the maximum of {}", version, max)
+            }
+            BuildError::VersionBitsMismatch { expected, found } => {
+                write!(
+                    f,
+                    "layout reserves {} version bits, expected {}",
+                    found, expected
+                )
+            }
+            BuildError::NotJsSafe { total } => {
+                write!(
+                    f,
+                    "layout uses {} bits, JavaScript-safe IDs allow at most 53",
+                    total
+                )
+            }
+            BuildError::NotI64Safe { total } => {
+                write!(
+                    f,
+                    "layout uses {} bits, i64-safe IDs allow at most 63",
+                    total
+                )
+            }
+            BuildError::NotU32Safe { total } => {
+                write!(f, "layout uses {} bits, short IDs allow at most 32", total)
+            }
+            BuildError::EpochBeforeUnixEpoch => {
+                write!(f, "epoch must not be earlier than the Unix epoch")
+            }
+            BuildError::ImplausibleClock { now_ms, floor_ms } => {
+                write!(
+                    f,
+                    "clock reads {} ms since the Unix epoch, earlier than the plausible minimum of {}",
+                    now_ms, floor_ms
+                )
+            }
+            BuildError::StateUnreadable(kind) => {
+                write!(f, "failed to read saved generator state: {}", kind)
+            }
+            BuildError::AlreadyInitialized => {
+                write!(f, "default generator is already initialized")
+            }
+            BuildError::EntropyUnavailable => {
+                write!(f, "random sequence modes need the getrandom feature")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockClock;
+
+    #[test]
+    fn test_builder_custom_layout() {
+        let generator = IdGenerator::builder()
+            .timestamp_bits(42)
+            .shard_bits(5)
+            .sequence_bits(16)
+            .shard_id(31)
+            .build()
+            .unwrap();
+
+        let id = generator.next_id();
+        assert_eq!(generator.shard_id(), 31);
+        assert_eq!(generator.layout().extract_shard_id(id), 31);
+        assert_eq!(generator.layout().max_sequence(), 65_535);
+        assert!(
+            generator.next_id() > id,
+            "IDs should be ordered by creation"
+        );
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_config() {
+        let result = IdGenerator::builder().timestamp_bits(50).build();
+        assert!(matches!(
+            result,
+            Err(BuildError::InvalidTotalBits { total: 73 })
+        ));
+
+        let result = IdGenerator::builder()
+            .timestamp_bits(49)
+            .shard_bits(5)
+            .sequence_bits(10)
+            .shard_id(32)
+            .build();
+        assert!(matches!(
+            result,
+            Err(BuildError::ShardIdOutOfRange {
+                shard_id: 32,
+                max: 31
+            })
+        ));
+    }
+
+    #[test]
+    fn test_builder_custom_epoch() {
+        let epoch_2020 = UNIX_EPOCH + Duration::from_millis(1_577_836_800_000);
+        let generator = IdGenerator::builder()
+            .epoch(epoch_2020)
+            .shard_id(1)
+            .build()
+            .unwrap();
+
+        let id = generator.next_id();
+        let extracted = generator.layout().extract_timestamp(id);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        assert!(extracted <= now && extracted >= now - 1000);
+        assert_eq!(
+            IdGenerator::extract_timestamp_with_epoch(id, 1_577_836_800_000),
+            extracted
+        );
+    }
+
+    #[test]
+    fn test_builder_seconds_unit() {
+        let generator = IdGenerator::builder()
+            .unit(TimeUnit::Seconds)
+            .timestamp_bits(32)
+            .shard_bits(15)
+            .sequence_bits(16)
+            .shard_id(3)
+            .build()
+            .unwrap();
+
+        let id1 = generator.next_id();
+        let id2 = generator.next_id();
+        assert!(id2 > id1);
+
+        let layout = generator.layout();
+        assert_eq!(layout.extract_timestamp(id1) % 1_000, 0);
+        assert_eq!(layout.extract_shard_id(id2), 3);
+    }
+
+    #[test]
+    fn test_builder_datacenter_and_worker() {
+        let generator = IdGenerator::builder()
+            .datacenter_bits(3)
+            .datacenter_id(5)
+            .worker_id(1000)
+            .build()
+            .unwrap();
+
+        let id = generator.next_id();
+        assert_eq!(generator.datacenter_id(), 5);
+        assert_eq!(generator.worker_id(), 1000);
+        assert_eq!(generator.layout().extract_datacenter(id), 5);
+        assert_eq!(generator.layout().extract_worker(id), 1000);
+
+        let result = IdGenerator::builder()
+            .datacenter_bits(3)
+            .datacenter_id(8)
+            .build();
+        assert!(matches!(
+            result,
+            Err(BuildError::NodeIdOutOfRange {
+                field: "datacenter",
+                ..
+            })
+        ));
+
+        let result = IdGenerator::builder().shard_id(1).worker_id(1).build();
+        assert!(matches!(result, Err(BuildError::ConflictingShardId)));
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn test_builder_random_sequence() {
+        let generator = IdGenerator::builder()
+            .sequence_bits(16)
+            .shard_bits(7)
+            .sequence_mode(SequenceMode::Random)
+            .shard_id(9)
+            .build()
+            .unwrap();
+
+        let ids: Vec<u64> = (0..2_000).map(|_| generator.next_id()).collect();
+        let sequences: std::collections::HashSet<u16> = ids
+            .iter()
+            .map(|&id| generator.layout().extract_sequence(id))
+            .collect();
+
+        // A counter would repeat 0, 1, 2, ... at every tick
+        assert!(sequences.len() > 1_000);
+        assert!(ids
+            .iter()
+            .all(|&id| generator.layout().extract_shard_id(id) == 9));
+    }
+
+    #[cfg(not(feature = "getrandom"))]
+    #[test]
+    fn test_random_sequence_needs_getrandom() {
+        for mode in [SequenceMode::Random, SequenceMode::RandomLowBits(4)] {
+            let result = IdGenerator::builder().sequence_mode(mode).build();
+            assert!(matches!(result, Err(BuildError::EntropyUnavailable)));
+        }
+    }
+
+    #[test]
+    fn test_builder_version_bits() {
+        let generator = IdGenerator::builder()
+            .version(2, 1)
+            .shard_id(5)
+            .build()
+            .unwrap();
+
+        let id = generator.next_id();
+        assert_eq!(id >> 62, 1);
+        assert_eq!(generator.layout().extract_version(id), 1);
+        assert_eq!(generator.layout().extract_shard_id(id), 5);
+    }
+
+    #[test]
+    fn test_builder_expiry_class() {
+        const SEVEN_DAYS: u8 = 1;
+        let generator = IdGenerator::builder()
+            .expiry_class_bits(2)
+            .shard_id(2000)
+            .build()
+            .unwrap();
+
+        let id = generator.next_id_with_expiry_class(SEVEN_DAYS);
+        assert_eq!(generator.layout().extract_expiry_class(id), SEVEN_DAYS);
+        assert_eq!(generator.layout().extract_shard_id(id), 2000);
+        assert_eq!(
+            generator.layout().extract_expiry_class(generator.next_id()),
+            0
+        );
+    }
+
+    #[test]
+    fn test_builder_time_source() {
+        #[derive(Debug)]
+        struct FixedClock;
+
+        impl TimeSource for FixedClock {
+            fn now(&self) -> Duration {
+                Duration::from_millis(crate::CUSTOM_EPOCH + 5_000)
+            }
+        }
+
+        let generator = IdGenerator::builder()
+            .time_source(FixedClock)
+            .shard_id(1)
+            .build()
+            .unwrap();
+        let id = generator.next_id();
+        assert_eq!(
+            generator.layout().extract_timestamp(id),
+            crate::CUSTOM_EPOCH + 5_000
+        );
+        assert_eq!(generator.layout().extract_sequence(generator.next_id()), 1);
+    }
+
+    #[test]
+    fn test_implausible_clock_rejected() {
+        let clock = MockClock::new(0);
+        let result = IdGenerator::builder()
+            .time_source(clock.clone())
+            .check_clock()
+            .build();
+        assert!(matches!(
+            result,
+            Err(BuildError::ImplausibleClock { now_ms: 0, .. })
+        ));
+
+        clock.set(1_800_000_000_000);
+        let floor = UNIX_EPOCH + Duration::from_millis(1_900_000_000_000);
+        let build = |floor| {
+            IdGenerator::builder()
+                .time_source(clock.clone())
+                .min_plausible_time(floor)
+                .build()
+        };
+        assert!(build(floor).is_err());
+        assert!(build(UNIX_EPOCH).is_ok());
+    }
+}
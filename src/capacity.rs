@@ -0,0 +1,183 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+/// Collision and exhaustion risk of a layout under an expected load, see
+/// [`Layout::assess_risk`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskReport {
+    /// Chance that two or more shards derive the same shard ID when IDs are
+    /// picked at random, e.g. hashed from host names. Such shards issue
+    /// colliding IDs whenever both are busy in the same tick.
+    pub shard_collision_probability: f64,
+    /// Chance that one tick receives more IDs than its sequence holds, so the
+    /// generator waits for the next tick, assuming Poisson arrivals.
+    pub tick_exhaustion_probability: f64,
+    /// Expected exhausted ticks per shard per second.
+    pub exhausted_ticks_per_second: f64,
+    /// Fraction of a shard's peak rate the load uses.
+    pub utilization: f64,
+}
+
+impl Layout {
+    /// Estimate risks for `shards` generators each issuing `ids_per_second`
+    /// on average, to check a custom layout before deploying it.
+    ///
+    /// ```
+    /// use banuid::Layout;
+    ///
+    /// let report = Layout::DEFAULT.assess_risk(100, 50_000.0);
+    /// assert!(report.shard_collision_probability > 0.4);
+    /// assert!(report.tick_exhaustion_probability < 1e-9);
+    /// ```
+    pub fn assess_risk(&self, shards: u64, ids_per_second: f64) -> RiskReport {
+        let space = self.max_shards() as f64;
+        let shard_collision_probability = if shards > self.max_shards() {
+            1.0
+        } else {
+            let pairs = shards as f64 * (shards as f64 - 1.0).max(0.0) / 2.0;
+            -(-pairs / space).exp_m1()
+        };
+
+        let ticks_per_second = 1_000_000.0 / self.unit().as_micros() as f64;
+        let mean = ids_per_second.max(0.0) / ticks_per_second;
+        let tick_exhaustion_probability = poisson_tail(mean, self.max_ids_per_tick());
+
+        RiskReport {
+            shard_collision_probability,
+            tick_exhaustion_probability,
+            exhausted_ticks_per_second: tick_exhaustion_probability * ticks_per_second,
+            utilization: ids_per_second.max(0.0) / self.max_ids_per_second() as f64,
+        }
+    }
+
+    /// Number of distinct sequence values, i.e. IDs per shard per tick.
+    pub const fn max_ids_per_tick(&self) -> u64 {
+        self.max_sequence() + 1
+    }
+
+    /// Peak IDs per shard per millisecond; fractional for second-based units.
+    pub fn max_ids_per_ms(&self) -> f64 {
+        self.max_ids_per_tick() as f64 * 1_000.0 / self.unit().as_micros() as f64
+    }
+
+    /// Peak IDs per shard per second.
+    pub fn max_ids_per_second(&self) -> u64 {
+        self.max_ids_per_tick()
+            .saturating_mul(1_000_000 / self.unit().as_micros())
+    }
+
+    /// Number of distinct shard IDs.
+    pub const fn max_shards(&self) -> u64 {
+        self.max_shard_id() + 1
+    }
+
+    /// Time span the timestamp field covers, saturating at `Duration::MAX`.
+    pub fn lifetime(&self) -> Duration {
+        let ticks = self.max_timestamp() as u128 + 1;
+        let micros = ticks * self.unit().as_micros() as u128;
+        let secs = micros / 1_000_000;
+        if secs > u64::MAX as u128 {
+            return Duration::MAX;
+        }
+        Duration::new(secs as u64, (micros % 1_000_000) as u32 * 1_000)
+    }
+
+    /// First instant the timestamp field can no longer represent, or `None`
+    /// if that lies beyond what `SystemTime` can hold.
+    pub fn timestamp_exhaustion_date(&self) -> Option<SystemTime> {
+        UNIX_EPOCH
+            .checked_add(Duration::from_millis(self.epoch()))?
+            .checked_add(self.lifetime())
+    }
+}
+
+// P(X > capacity) for X ~ Poisson(mean), summing the CDF in log space
+fn poisson_tail(mean: f64, capacity: u64) -> f64 {
+    if mean <= 0.0 {
+        return 0.0;
+    }
+    let ln_mean = mean.ln();
+    let mut ln_term = -mean;
+    let mut cdf = ln_term.exp();
+    for k in 1..=capacity {
+        ln_term += ln_mean - (k as f64).ln();
+        cdf += ln_term.exp();
+    }
+    (1.0 - cdf).clamp(0.0, 1.0)
+}
+
+impl IdGenerator {
+    /// Peak IDs this generator can issue per millisecond.
+    pub fn max_ids_per_ms(&self) -> f64 {
+        self.layout().max_ids_per_ms()
+    }
+
+    /// Number of distinct shards in this generator's layout.
+    pub fn max_shards(&self) -> u64 {
+        self.layout().max_shards()
+    }
+
+    /// When this generator's timestamp field runs out.
+    pub fn timestamp_exhaustion_date(&self) -> Option<SystemTime> {
+        self.layout().timestamp_exhaustion_date()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TimeUnit;
+
+    #[test]
+    fn test_default_capacity() {
+        let generator = IdGenerator::with_shard_id(1);
+        assert_eq!(generator.max_ids_per_ms(), 1_024.0);
+        assert_eq!(generator.max_shards(), 8_192);
+
+        // 2^41 ms after 2024-01-01 is 2093-09-06
+        let exhausted = generator.timestamp_exhaustion_date().unwrap();
+        let millis = exhausted.duration_since(UNIX_EPOCH).unwrap().as_millis();
+        assert_eq!(millis, 1_704_067_200_000 + (1u128 << 41));
+    }
+
+    #[test]
+    fn test_assess_risk() {
+        let layout = Layout::DEFAULT;
+
+        let report = layout.assess_risk(2, 0.0);
+        assert!((report.shard_collision_probability - 1.0 / 8_192.0).abs() < 1e-8);
+        assert_eq!(report.tick_exhaustion_probability, 0.0);
+        assert_eq!(layout.assess_risk(1, 0.0).shard_collision_probability, 0.0);
+        assert_eq!(
+            layout.assess_risk(8_193, 0.0).shard_collision_probability,
+            1.0
+        );
+
+        // Running at exactly peak rate overflows about half of all ticks
+        let report = layout.assess_risk(1, 1_024_000.0);
+        assert_eq!(report.utilization, 1.0);
+        assert!((0.45..0.5).contains(&report.tick_exhaustion_probability));
+        assert!((450.0..500.0).contains(&report.exhausted_ticks_per_second));
+
+        let report = layout.assess_risk(1, 10_240_000.0);
+        assert_eq!(report.tick_exhaustion_probability, 1.0);
+    }
+
+    #[test]
+    fn test_capacity_respects_unit() {
+        let seconds = Layout::DEFAULT.with_unit(TimeUnit::Seconds);
+        assert_eq!(seconds.max_ids_per_second(), 1_024);
+        assert_eq!(seconds.max_ids_per_ms(), 1.024);
+        assert_eq!(seconds.lifetime(), Duration::from_secs(1 << 41));
+
+        let sonyflake = Layout::SONYFLAKE;
+        assert_eq!(sonyflake.max_ids_per_second(), 25_600);
+        assert_eq!(sonyflake.max_shards(), 65_536);
+
+        let wide = Layout::new(64, 0, 0).unwrap().with_unit(TimeUnit::Seconds);
+        assert_eq!(wide.lifetime(), Duration::MAX);
+        assert_eq!(wide.timestamp_exhaustion_date(), None);
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+impl Layout {
+    /// Creation time of `id` as a UTC date-time, or `None` if it lies outside
+    /// what `chrono` can represent.
+    pub fn extract_datetime(&self, id: u64) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(i64::try_from(self.extract_timestamp(id)).ok()?)
+    }
+}
+
+impl IdGenerator {
+    /// Creation time of an ID minted with the default layout.
+    pub fn extract_datetime(id: u64) -> DateTime<Utc> {
+        Layout::DEFAULT
+            .extract_datetime(id)
+            .expect("default layout timestamps are always representable")
+    }
+
+    /// Creation time of an ID minted with the default layout, formatted as
+    /// ISO 8601 with millisecond precision, e.g. `2024-01-01T00:00:00.000Z`.
+    pub fn extract_iso8601(id: u64) -> String {
+        Self::extract_datetime(id).to_rfc3339_opts(SecondsFormat::Millis, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_datetime() {
+        let id = Layout::DEFAULT.encode(1_500, 7, 0);
+        let datetime = IdGenerator::extract_datetime(id);
+        assert_eq!(datetime.timestamp_millis(), 1_704_067_201_500);
+        assert_eq!(IdGenerator::extract_iso8601(id), "2024-01-01T00:00:01.500Z");
+
+        let generator = IdGenerator::snowflake(1, 1);
+        let id = generator.next_id();
+        let datetime = generator.layout().extract_datetime(id).unwrap();
+        assert_eq!(
+            datetime.timestamp_millis() as u64,
+            generator.layout().extract_timestamp(id)
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Clock an [`IdGenerator`](crate::IdGenerator) reads timestamps from.
+///
+/// Install one with [`IdGeneratorBuilder::time_source`](crate::IdGeneratorBuilder::time_source)
+/// to drive generation from a simulated, monotonic-anchored or coarse clock.
+pub trait TimeSource: fmt::Debug + Send + Sync {
+    /// Time elapsed since the Unix epoch.
+    fn now(&self) -> Duration;
+
+    /// Wait for `duration` to pass on this clock; used when a tick's sequence is exhausted.
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Clock anomaly reported to a hook registered with
+/// [`IdGeneratorBuilder::on_clock_event`](crate::IdGeneratorBuilder::on_clock_event).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ClockEvent {
+    /// The clock read earlier than the last issued timestamp.
+    MovedBackwards { by_ms: u64 },
+    /// The wall clock advanced further than the monotonic clock between two
+    /// IDs, by more than the configured threshold.
+    JumpedForward { by_ms: u64 },
+    /// The clock repeated up to one second, as around a leap second; IDs
+    /// continue from the held logical timestamp until it catches up.
+    LeapSecond { by_ms: u64 },
+    /// A tick's sequence ran out and generation had to wait for the next tick.
+    SequenceExhausted,
+}
+
+/// What a generator does when the wall clock steps forward by more than the
+/// [forward jump threshold](crate::IdGeneratorBuilder::forward_jump_threshold),
+/// e.g. after NTP corrects a container resumed from suspend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ForwardStepPolicy {
+    /// Use the new time immediately.
+    #[default]
+    Accept,
+    /// Ignore the step and advance at twice the monotonic clock's rate until
+    /// the wall clock is reached, so IDs never leap ahead.
+    Clamp,
+    /// Fail the call that observes the step with
+    /// [`BanuidError::ClockJumpedForward`](crate::BanuidError::ClockJumpedForward);
+    /// later calls use the new time.
+    Error,
+}
+
+/// Shareable callback receiving [`ClockEvent`]s.
+#[derive(Clone)]
+pub(crate) struct ClockHook(pub(crate) Arc<dyn Fn(ClockEvent) + Send + Sync>);
+
+impl fmt::Debug for ClockHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ClockHook")
+    }
+}
+
+/// The system wall clock, used unless another source is configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl TimeSource for SystemClock {
+    fn now(&self) -> Duration {
+        crate::current_time()
+    }
+}
+
+/// Wall clock anchored to [`Instant`], so NTP adjustments cannot make it run backwards.
+///
+/// The wall clock is read once; afterwards time advances with the monotonic
+/// clock. Every `reanchor_every` the wall clock is read again and the drift
+/// between the two is corrected by at most `max_correction`, never moving
+/// the reported time backwards.
+#[derive(Debug)]
+pub struct MonotonicClock {
+    wall: Box<dyn TimeSource>,
+    reanchor_every: Duration,
+    max_correction: Duration,
+    anchor: Mutex<Anchor>,
+}
+
+#[derive(Debug)]
+struct Anchor {
+    wall: Duration,
+    instant: Instant,
+    last: Duration,
+}
+
+impl MonotonicClock {
+    /// Anchor to the system clock, re-anchoring every minute by at most 10 ms.
+    pub fn new() -> Self {
+        Self::anchored_to(SystemClock)
+    }
+
+    /// Anchor to another wall clock source.
+    pub fn anchored_to(wall: impl TimeSource + 'static) -> Self {
+        let now = wall.now();
+        MonotonicClock {
+            wall: Box::new(wall),
+            reanchor_every: Duration::from_secs(60),
+            max_correction: Duration::from_millis(10),
+            anchor: Mutex::new(Anchor {
+                wall: now,
+                instant: Instant::now(),
+                last: now,
+            }),
+        }
+    }
+
+    /// Re-read the wall clock every `interval`, correcting drift by at most `max_correction`.
+    pub fn with_reanchor(mut self, interval: Duration, max_correction: Duration) -> Self {
+        self.reanchor_every = interval;
+        self.max_correction = max_correction;
+        self
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for MonotonicClock {
+    fn now(&self) -> Duration {
+        let mut anchor = self.anchor.lock().unwrap_or_else(PoisonError::into_inner);
+        let elapsed = anchor.instant.elapsed();
+        let mut now = anchor.wall + elapsed;
+
+        if elapsed >= self.reanchor_every {
+            let wall = self.wall.now();
+            now = if wall > now {
+                now + (wall - now).min(self.max_correction)
+            } else {
+                now - (now - wall).min(self.max_correction)
+            };
+            anchor.wall = now;
+            anchor.instant = Instant::now();
+        }
+
+        anchor.last = anchor.last.max(now);
+        anchor.last
+    }
+}
+
+/// Clock refreshed by a background thread, so reading it is a single atomic
+/// load instead of a system call per ID.
+///
+/// Readings lag the wall clock by up to the refresh interval. Clones share
+/// the thread, which exits once the last clone is dropped.
+#[derive(Debug, Clone)]
+pub struct CachedClock {
+    micros: Arc<AtomicU64>,
+}
+
+impl CachedClock {
+    /// Start a thread refreshing the cached time every `interval`.
+    pub fn spawn(interval: Duration) -> Self {
+        Self::spawn_from(SystemClock, interval)
+    }
+
+    /// Like [`spawn`](Self::spawn), caching another time source.
+    pub fn spawn_from(source: impl TimeSource + 'static, interval: Duration) -> Self {
+        let micros = Arc::new(AtomicU64::new(source.now().as_micros() as u64));
+        let cached = Arc::downgrade(&micros);
+        thread::Builder::new()
+            .name("banuid-clock".into())
+            .spawn(move || {
+                while let Some(micros) = cached.upgrade() {
+                    micros.store(source.now().as_micros() as u64, Ordering::Relaxed);
+                    drop(micros);
+                    thread::sleep(interval);
+                }
+            })
+            .expect("failed to spawn clock thread");
+        CachedClock { micros }
+    }
+}
+
+impl TimeSource for CachedClock {
+    fn now(&self) -> Duration {
+        Duration::from_micros(self.micros.load(Ordering::Relaxed))
+    }
+}
+
+/// High-resolution clock reading the CPU timestamp counter through `quanta`,
+/// anchored to the wall clock once at construction.
+///
+/// Like [`MonotonicClock`] it never follows wall clock adjustments made after
+/// construction.
+#[cfg(feature = "quanta")]
+#[derive(Debug, Clone)]
+pub struct QuantaClock {
+    clock: quanta::Clock,
+    start: quanta::Instant,
+    wall: Duration,
+}
+
+#[cfg(feature = "quanta")]
+impl QuantaClock {
+    pub fn new() -> Self {
+        let clock = quanta::Clock::new();
+        let start = clock.now();
+        QuantaClock {
+            clock,
+            start,
+            wall: crate::current_time(),
+        }
+    }
+}
+
+#[cfg(feature = "quanta")]
+impl Default for QuantaClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "quanta")]
+impl TimeSource for QuantaClock {
+    fn now(&self) -> Duration {
+        self.wall + self.clock.now().duration_since(self.start)
+    }
+}
+
+/// Wall clock read through `CLOCK_REALTIME_COARSE`, which skips the precise
+/// timer read of a regular `clock_gettime` call.
+///
+/// Its resolution is the kernel tick (typically 1 to 4 ms, see
+/// `clock_getres`), so coarse-unit layouts benefit most.
+#[cfg(all(feature = "coarse-clock", target_os = "linux"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CoarseClock;
+
+#[cfg(all(feature = "coarse-clock", target_os = "linux"))]
+impl TimeSource for CoarseClock {
+    fn now(&self) -> Duration {
+        let mut ts = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: `ts` is a valid, writable timespec for the duration of the call
+        let result = unsafe { libc::clock_gettime(libc::CLOCK_REALTIME_COARSE, &mut ts) };
+        if result != 0 || ts.tv_sec < 0 {
+            return crate::current_time();
+        }
+        Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockClock;
+
+    #[test]
+    fn test_monotonic_clock_ignores_wall_steps() {
+        let wall = MockClock::new(1_000_000);
+        let clock = MonotonicClock::anchored_to(wall.clone());
+
+        wall.set(5_000);
+        let first = clock.now();
+        assert!(first >= Duration::from_millis(1_000_000));
+        wall.set(2_000_000);
+        assert!(clock.now() >= first);
+        assert!(clock.now() < Duration::from_millis(1_100_000));
+    }
+
+    #[test]
+    fn test_monotonic_clock_bounded_correction() {
+        let wall = MockClock::new(1_000_000);
+        let clock = MonotonicClock::anchored_to(wall.clone())
+            .with_reanchor(Duration::ZERO, Duration::from_millis(5));
+
+        wall.set(1_010_000);
+        let ahead = clock.now();
+        assert!(ahead >= Duration::from_millis(1_000_005));
+        assert!(ahead < Duration::from_millis(1_001_000));
+
+        wall.set(900_000);
+        let mut previous = ahead;
+        for _ in 0..10 {
+            let now = clock.now();
+            assert!(now >= previous);
+            previous = now;
+        }
+    }
+
+    #[test]
+    fn test_cached_clock_refreshes() {
+        let wall = MockClock::new(1_000);
+        let clock = CachedClock::spawn_from(wall.clone(), Duration::from_millis(1));
+        assert_eq!(clock.now(), Duration::from_millis(1_000));
+
+        wall.set(2_000);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while clock.now() != Duration::from_millis(2_000) {
+            assert!(Instant::now() < deadline, "cached clock never refreshed");
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let system = CachedClock::spawn(Duration::from_millis(1));
+        let lag = SystemClock.now().saturating_sub(system.now());
+        assert!(lag < Duration::from_secs(1));
+    }
+
+    #[cfg(feature = "quanta")]
+    #[test]
+    fn test_quanta_clock_tracks_wall_clock() {
+        let clock = QuantaClock::new();
+        let first = clock.now();
+        thread::sleep(Duration::from_millis(5));
+        let second = clock.now();
+        assert!(second >= first + Duration::from_millis(4));
+
+        let drift = SystemClock.now().abs_diff(second);
+        assert!(drift < Duration::from_secs(1));
+    }
+
+    #[cfg(all(feature = "coarse-clock", target_os = "linux"))]
+    #[test]
+    fn test_coarse_clock_tracks_wall_clock() {
+        let drift = SystemClock.now().abs_diff(CoarseClock.now());
+        assert!(drift < Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+/// An [`IdGenerator`] whose layout is fixed by const generics.
+///
+/// Widths are checked at compile time and extraction folds down to
+/// constant shifts and masks.
+///
+/// ```
+/// use banuid::ConstIdGenerator;
+///
+/// type WideSequence = ConstIdGenerator<41, 8, 15>;
+///
+/// let generator = WideSequence::with_shard_id(200);
+/// let id = generator.next_id();
+/// assert_eq!(WideSequence::extract_shard_id(id), 200);
+/// ```
+///
+/// Layouts that do not add up to 63 or 64 bits are rejected by the compiler:
+///
+/// ```compile_fail
+/// let generator = banuid::ConstIdGenerator::<41, 13, 11>::new();
+/// ```
+pub struct ConstIdGenerator<const TS: u8, const SHARD: u8, const SEQ: u8> {
+    inner: IdGenerator,
+}
+
+impl<const TS: u8, const SHARD: u8, const SEQ: u8> ConstIdGenerator<TS, SHARD, SEQ> {
+    /// The layout described by the const parameters.
+    pub const LAYOUT: Layout = match Layout::new(TS, SHARD, SEQ) {
+        Ok(layout) => layout,
+        Err(_) => panic!("timestamp, shard and sequence bits must add up to 63 or 64"),
+    };
+
+    pub fn new() -> Self {
+        Self::with_shard_id(crate::derive_shard_id())
+    }
+
+    /// Only the low `SHARD` bits of `shard_id` are used.
+    pub fn with_shard_id(shard_id: u16) -> Self {
+        let layout = Self::LAYOUT;
+        let shard_id = (shard_id as u64 & layout.max_shard_id()) as u16;
+        ConstIdGenerator {
+            inner: IdGenerator::from_parts(shard_id, layout),
+        }
+    }
+
+    /// Generate an ID using this instance
+    pub fn generate(&self) -> u64 {
+        self.inner.next_id()
+    }
+
+    pub fn next_id(&self) -> u64 {
+        self.inner.next_id()
+    }
+
+    pub const fn extract_timestamp(id: u64) -> u64 {
+        Self::LAYOUT.extract_timestamp(id)
+    }
+
+    pub const fn extract_shard_id(id: u64) -> u16 {
+        Self::LAYOUT.extract_shard_id(id)
+    }
+
+    pub const fn extract_sequence(id: u64) -> u16 {
+        Self::LAYOUT.extract_sequence(id)
+    }
+
+    pub fn shard_id(&self) -> u16 {
+        self.inner.shard_id()
+    }
+
+    /// The underlying runtime generator, for APIs not mirrored here.
+    pub fn as_generator(&self) -> &IdGenerator {
+        &self.inner
+    }
+}
+
+impl<const TS: u8, const SHARD: u8, const SEQ: u8> Default for ConstIdGenerator<TS, SHARD, SEQ> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_const_generator_matches_runtime_layout() {
+        type Gen = ConstIdGenerator<42, 6, 15>;
+        let generator = Gen::with_shard_id(63);
+        let id = generator.next_id();
+
+        let layout = Layout::new(42, 6, 15).unwrap();
+        assert_eq!(Gen::LAYOUT, layout);
+        assert_eq!(Gen::extract_shard_id(id), 63);
+        assert_eq!(Gen::extract_timestamp(id), layout.extract_timestamp(id));
+        assert_eq!(generator.as_generator().layout(), &layout);
+
+        const DECODED: u16 = ConstIdGenerator::<41, 13, 10>::extract_shard_id(42 << 10);
+        assert_eq!(DECODED, 42);
+    }
+}
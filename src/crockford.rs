@@ -0,0 +1,157 @@
+use crate::id::{Id, ParseIdError};
+
+pub(crate) const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Extra symbols for check values 32 to 36.
+const CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+
+/// Characters needed for any `u64`.
+const CROCKFORD_LEN: usize = 13;
+
+/// Encode `id` as 13 Crockford Base32 characters, zero-padded so encoded
+/// IDs sort like the IDs themselves.
+///
+/// The alphabet leaves out I, L, O and U, so IDs survive being read aloud or
+/// retyped; [`decode_crockford`] also accepts lowercase, hyphens and the
+/// commonly confused letters.
+///
+/// ```
+/// let encoded = banuid::encode_crockford(1234567890123456789);
+/// assert_eq!(encoded, "128GGYHYYK08N");
+/// assert_eq!(banuid::decode_crockford("128g-gyhy-yk08n"), Ok(1234567890123456789));
+/// ```
+pub fn encode_crockford(id: u64) -> String {
+    let mut buf = [0u8; CROCKFORD_LEN];
+    encode_crockford_into(id, &mut buf);
+    // Only ASCII from the alphabet was written
+    String::from_utf8(buf.to_vec()).unwrap()
+}
+
+/// Write [`encode_crockford`]'s 13 characters to the start of `buf` without
+/// allocating, returning the number of bytes written.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than 13 bytes.
+pub fn encode_crockford_into(id: u64, buf: &mut [u8]) -> usize {
+    let mut value = id;
+    for slot in buf[..CROCKFORD_LEN].iter_mut().rev() {
+        *slot = CROCKFORD_ALPHABET[(value & 0x1F) as usize];
+        value >>= 5;
+    }
+    CROCKFORD_LEN
+}
+
+/// Like [`encode_crockford`], followed by a check symbol that catches a
+/// single mistyped character or two swapped adjacent characters.
+pub fn encode_crockford_with_check(id: u64) -> String {
+    let mut encoded = encode_crockford(id);
+    encoded.push(check_symbol(id) as char);
+    encoded
+}
+
+/// Decode a Crockford Base32 ID, ignoring case and hyphens.
+pub fn decode_crockford(s: &str) -> Result<u64, ParseIdError> {
+    let digits: Vec<u8> = s.bytes().filter(|&b| b != b'-').collect();
+    if digits.is_empty() || digits.len() > CROCKFORD_LEN {
+        return Err(ParseIdError::InvalidLength(digits.len()));
+    }
+    digits.iter().try_fold(0u64, |value, &byte| {
+        let digit = decode_symbol(byte).ok_or(ParseIdError::InvalidChar(byte as char))?;
+        if value >> 59 != 0 {
+            return Err(ParseIdError::Overflow);
+        }
+        Ok((value << 5) | digit as u64)
+    })
+}
+
+/// Decode an ID written by [`encode_crockford_with_check`], verifying the
+/// trailing check symbol.
+pub fn decode_crockford_with_check(s: &str) -> Result<u64, ParseIdError> {
+    let Some((split, check)) = s.char_indices().next_back() else {
+        return Err(ParseIdError::InvalidLength(0));
+    };
+    if !check.is_ascii() {
+        return Err(ParseIdError::InvalidChar(check));
+    }
+    let id = decode_crockford(&s[..split])?;
+    if !check_symbol(id).eq_ignore_ascii_case(&(check as u8)) {
+        return Err(ParseIdError::CheckSymbolMismatch);
+    }
+    Ok(id)
+}
+
+impl<T> Id<T> {
+    /// The ID as 13 Crockford Base32 characters, see [`encode_crockford`].
+    pub fn to_crockford(&self) -> String {
+        encode_crockford(self.as_u64())
+    }
+}
+
+fn check_symbol(id: u64) -> u8 {
+    let value = (id % 37) as usize;
+    if value < 32 {
+        CROCKFORD_ALPHABET[value]
+    } else {
+        CHECK_SYMBOLS[value - 32]
+    }
+}
+
+pub(crate) fn decode_symbol(byte: u8) -> Option<u8> {
+    let byte = byte.to_ascii_uppercase();
+    match byte {
+        b'O' => Some(0),
+        b'I' | b'L' => Some(1),
+        b'U' => None,
+        _ => CROCKFORD_ALPHABET
+            .iter()
+            .position(|&c| c == byte)
+            .map(|pos| pos as u8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crockford_round_trip() {
+        let ids = [0, 1, 31, 32, 1 << 40, u64::MAX - 1, u64::MAX];
+        for pair in ids.windows(2) {
+            assert!(encode_crockford(pair[0]) < encode_crockford(pair[1]));
+        }
+        for id in ids {
+            assert_eq!(decode_crockford(&encode_crockford(id)), Ok(id));
+            assert_eq!(
+                decode_crockford_with_check(&encode_crockford_with_check(id)),
+                Ok(id)
+            );
+        }
+        assert_eq!(encode_crockford(u64::MAX), "FZZZZZZZZZZZZ");
+        assert_eq!(
+            decode_crockford("G000000000000"),
+            Err(ParseIdError::Overflow)
+        );
+        assert_eq!(decode_crockford("OIL"), Ok(0b00001_00001));
+        assert_eq!(decode_crockford("1U"), Err(ParseIdError::InvalidChar('U')));
+        assert_eq!(decode_crockford("--"), Err(ParseIdError::InvalidLength(0)));
+    }
+
+    #[test]
+    fn test_crockford_check_symbol() {
+        // 36 % 37 needs one of the extra check symbols
+        assert_eq!(encode_crockford_with_check(36), "0000000000014U");
+        assert_eq!(decode_crockford_with_check("0000000000014u"), Ok(36));
+        assert_eq!(
+            decode_crockford_with_check("0000000000015U"),
+            Err(ParseIdError::CheckSymbolMismatch)
+        );
+        assert_eq!(
+            decode_crockford_with_check("0000000000014é"),
+            Err(ParseIdError::InvalidChar('é'))
+        );
+
+        let id = Id::<()>::from_u64(1234567890123456789).unwrap();
+        assert_eq!(id.to_crockford(), "128GGYHYYK08N");
+    }
+}
@@ -0,0 +1,293 @@
+use std::fmt;
+
+use crate::builder::BuildError;
+use crate::layout::{FieldOrder, Layout, LayoutSpec, Rounding, TimeUnit};
+
+/// The fields of a decoded ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub struct IdComponents {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    pub shard_id: u16,
+    pub sequence: u16,
+}
+
+impl fmt::Display for IdComponents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} shard={} seq={}",
+            crate::explain::rfc3339_millis(self.timestamp_ms),
+            self.shard_id,
+            self.sequence
+        )
+    }
+}
+
+/// Decode `id` using a layout description, e.g. one received from another service.
+pub fn decode_with(spec: &LayoutSpec, id: u64) -> Result<IdComponents, BuildError> {
+    Ok(spec.to_layout()?.decode(id))
+}
+
+/// Decode a foreign `[timestamp | node | sequence]` snowflake with a
+/// millisecond timestamp, without describing it as a [`LayoutSpec`] first.
+///
+/// ```
+/// use banuid::decode_foreign;
+///
+/// // A Discord snowflake: 42-bit timestamp since 2015, 10-bit node, 12-bit increment
+/// let components = decode_foreign(175928847299117063, 1420070400000, 42, 10, 12).unwrap();
+/// assert_eq!(components.timestamp_ms, 1462015105796);
+/// assert_eq!(components.sequence, 7);
+/// ```
+pub fn decode_foreign(
+    id: u64,
+    epoch_ms: u64,
+    timestamp_bits: u8,
+    node_bits: u8,
+    sequence_bits: u8,
+) -> Result<IdComponents, BuildError> {
+    let spec = LayoutSpec {
+        epoch: epoch_ms,
+        timestamp_bits,
+        shard_bits: node_bits,
+        sequence_bits,
+        unit: TimeUnit::Millis,
+        field_order: FieldOrder::ShardFirst,
+        rounding: Rounding::Floor,
+        datacenter_bits: 0,
+        version_bits: 0,
+        version: 0,
+        expiry_class_bits: 0,
+        checksum_bits: 0,
+    };
+    decode_with(&spec, id)
+}
+
+/// Decodes IDs minted under several versioned layouts, dispatching on the
+/// version bits of each ID.
+///
+/// ```
+/// use banuid::{Layout, VersionedLayouts};
+///
+/// let v1 = Layout::DEFAULT.with_version(2, 1).unwrap();
+/// let v2 = Layout::new(41, 10, 13).unwrap().with_version(2, 2).unwrap();
+///
+/// let mut layouts = VersionedLayouts::new(2).unwrap();
+/// layouts.register(v1).unwrap();
+/// layouts.register(v2).unwrap();
+///
+/// let id = v2.encode(1_000, 3, 4);
+/// assert_eq!(layouts.extract_shard_id(id), Ok(3));
+/// assert!(layouts.layout_for(v1.encode(1, 1, 1) | (3 << 62)).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct VersionedLayouts {
+    version_bits: u8,
+    layouts: Vec<Layout>,
+}
+
+impl VersionedLayouts {
+    /// Create an empty set for layouts reserving `version_bits` version bits,
+    /// at most 8 as in [`Layout::with_version`].
+    pub fn new(version_bits: u8) -> Result<Self, BuildError> {
+        if version_bits > 8 {
+            return Err(BuildError::FieldTooWide {
+                field: "version",
+                bits: version_bits,
+                max: 8,
+            });
+        }
+        Ok(VersionedLayouts {
+            version_bits,
+            layouts: Vec::new(),
+        })
+    }
+
+    /// Add a layout, replacing any layout already registered for its version.
+    ///
+    /// Versions live in the top bits of the ID, so layouts must use all 64 bits.
+    pub fn register(&mut self, layout: Layout) -> Result<(), BuildError> {
+        if layout.version_bits() != self.version_bits {
+            return Err(BuildError::VersionBitsMismatch {
+                expected: self.version_bits,
+                found: layout.version_bits(),
+            });
+        }
+        if layout.total_bits() != 64 {
+            return Err(BuildError::InvalidTotalBits {
+                total: layout.total_bits() as u32,
+            });
+        }
+
+        self.layouts.retain(|l| l.version() != layout.version());
+        self.layouts.push(layout);
+        Ok(())
+    }
+
+    pub fn version_bits(&self) -> u8 {
+        self.version_bits
+    }
+
+    /// Version stored in the top bits of `id`.
+    pub fn extract_version(&self, id: u64) -> u8 {
+        if self.version_bits == 0 {
+            0
+        } else {
+            (id >> (64 - self.version_bits as u32)) as u8
+        }
+    }
+
+    /// The layout `id` was minted with, or an error for unknown versions.
+    pub fn layout_for(&self, id: u64) -> Result<&Layout, DecodeError> {
+        let version = self.extract_version(id);
+        self.layouts
+            .iter()
+            .find(|layout| layout.version() == version)
+            .ok_or(DecodeError::UnknownVersion(version))
+    }
+
+    pub fn decode(&self, id: u64) -> Result<IdComponents, DecodeError> {
+        self.layout_for(id).map(|layout| layout.decode(id))
+    }
+
+    pub fn extract_timestamp(&self, id: u64) -> Result<u64, DecodeError> {
+        self.layout_for(id)
+            .map(|layout| layout.extract_timestamp(id))
+    }
+
+    pub fn extract_shard_id(&self, id: u64) -> Result<u16, DecodeError> {
+        self.layout_for(id)
+            .map(|layout| layout.extract_shard_id(id))
+    }
+
+    pub fn extract_sequence(&self, id: u64) -> Result<u16, DecodeError> {
+        self.layout_for(id)
+            .map(|layout| layout.extract_sequence(id))
+    }
+}
+
+/// Errors returned when an ID cannot be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The ID's version bits match no known layout.
+    UnknownVersion(u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnknownVersion(version) => {
+                write!(f, "unknown ID layout version {}", version)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_with_spec() {
+        let spec = Layout::SNOWFLAKE.spec();
+        let id = Layout::SNOWFLAKE.encode(5, 33, 7);
+
+        assert_eq!(
+            decode_with(&spec, id),
+            Ok(IdComponents {
+                timestamp_ms: crate::presets::TWITTER_EPOCH + 5,
+                shard_id: 33,
+                sequence: 7,
+            })
+        );
+    }
+
+    #[test]
+    fn test_components_display() {
+        let components = crate::decode(Layout::DEFAULT.encode(0, 42, 3));
+        assert_eq!(components.shard_id, 42);
+        assert_eq!(
+            components.to_string(),
+            "2024-01-01T00:00:00.000Z shard=42 seq=3"
+        );
+    }
+
+    #[test]
+    fn test_decode_foreign() {
+        // A tweet ID from 2022-06-05
+        let id: u64 = 1_533_461_183_066_062_848;
+        let components = decode_foreign(id, crate::presets::TWITTER.epoch, 41, 10, 12).unwrap();
+        assert_eq!(
+            components,
+            crate::presets::TWITTER.to_layout().unwrap().decode(id)
+        );
+        assert_eq!(components.timestamp_ms, 1_654_440_611_031);
+
+        assert!(decode_foreign(id, 0, 41, 17, 6).is_err());
+        assert!(decode_foreign(id, 0, 41, 13, 12).is_err());
+    }
+
+    #[test]
+    fn test_versioned_dispatch() {
+        let v0 = Layout::DEFAULT.with_version(2, 0).unwrap();
+        let v1 = Layout::new(41, 8, 15).unwrap().with_version(2, 1).unwrap();
+        let mut layouts = VersionedLayouts::new(2).unwrap();
+        layouts.register(v0).unwrap();
+        layouts.register(v1).unwrap();
+
+        let old = v0.encode(10, 8000, 1000);
+        let new = v1.encode(10, 200, 30_000);
+        assert_eq!(layouts.extract_shard_id(old), Ok(8000));
+        assert_eq!(layouts.extract_shard_id(new), Ok(200));
+        assert_eq!(layouts.extract_sequence(new), Ok(30_000));
+        assert_eq!(layouts.extract_timestamp(new), Ok(crate::CUSTOM_EPOCH + 10));
+
+        let unknown = new | (3 << 62);
+        assert_eq!(
+            layouts.layout_for(unknown),
+            Err(DecodeError::UnknownVersion(3))
+        );
+    }
+
+    #[test]
+    fn test_version_bits_validated() {
+        assert!(matches!(
+            VersionedLayouts::new(65),
+            Err(BuildError::FieldTooWide {
+                field: "version",
+                bits: 65,
+                max: 8
+            })
+        ));
+        assert!(matches!(
+            Layout::new_short(0, 16, 16).unwrap().with_version(1, 0),
+            Err(BuildError::FieldTooWide {
+                field: "version",
+                max: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_register_rejects_mismatched_bits() {
+        let mut layouts = VersionedLayouts::new(2).unwrap();
+        let result = layouts.register(Layout::DEFAULT.with_version(3, 1).unwrap());
+        assert!(matches!(
+            result,
+            Err(BuildError::VersionBitsMismatch {
+                expected: 2,
+                found: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+use crate::layout::Layout;
+
+/// Reports IDs seen more than once within a sliding window of ID timestamps,
+/// e.g. to catch two nodes configured with the same shard ID.
+///
+/// The window follows the newest timestamp observed; IDs older than that by
+/// more than the window are forgotten, keeping memory bounded by the ID rate.
+///
+/// ```
+/// use banuid::{DuplicateDetector, Layout};
+/// use std::time::Duration;
+///
+/// let mut detector = DuplicateDetector::new(Layout::DEFAULT, Duration::from_secs(60));
+/// let id = Layout::DEFAULT.encode(10_000, 7, 0);
+/// assert!(!detector.observe(id));
+/// assert!(detector.observe(id));
+/// assert_eq!(detector.duplicates_by_shard(), vec![(7, 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DuplicateDetector {
+    layout: Layout,
+    window_ms: u64,
+    seen: HashSet<u64>,
+    // IDs by timestamp, so eviction doesn't depend on arrival order
+    by_timestamp: BTreeMap<u64, Vec<u64>>,
+    newest_ms: u64,
+    duplicates: HashMap<u16, u64>,
+}
+
+impl DuplicateDetector {
+    pub fn new(layout: Layout, window: Duration) -> Self {
+        DuplicateDetector {
+            layout,
+            window_ms: window.as_millis() as u64,
+            seen: HashSet::new(),
+            by_timestamp: BTreeMap::new(),
+            newest_ms: 0,
+            duplicates: HashMap::new(),
+        }
+    }
+
+    /// Record `id`, returning whether it was already seen within the window.
+    pub fn observe(&mut self, id: u64) -> bool {
+        let timestamp_ms = self.layout.extract_timestamp(id);
+        self.newest_ms = self.newest_ms.max(timestamp_ms);
+        let cutoff = self.newest_ms.saturating_sub(self.window_ms);
+        if timestamp_ms < cutoff {
+            return false;
+        }
+        while let Some(oldest) = self.by_timestamp.first_entry() {
+            if *oldest.key() >= cutoff {
+                break;
+            }
+            for old_id in oldest.remove() {
+                self.seen.remove(&old_id);
+            }
+        }
+
+        if self.seen.contains(&id) {
+            *self
+                .duplicates
+                .entry(self.layout.extract_shard_id(id))
+                .or_insert(0) += 1;
+            return true;
+        }
+        self.seen.insert(id);
+        self.by_timestamp.entry(timestamp_ms).or_default().push(id);
+        false
+    }
+
+    /// Number of duplicates reported so far.
+    pub fn duplicate_count(&self) -> u64 {
+        self.duplicates.values().sum()
+    }
+
+    /// Duplicates reported per shard, ordered by shard ID.
+    pub fn duplicates_by_shard(&self) -> Vec<(u16, u64)> {
+        let mut counts: Vec<_> = self.duplicates.iter().map(|(&s, &n)| (s, n)).collect();
+        counts.sort_unstable();
+        counts
+    }
+
+    /// Number of IDs currently remembered.
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Forget all IDs and counts.
+    pub fn reset(&mut self) {
+        self.seen.clear();
+        self.by_timestamp.clear();
+        self.newest_ms = 0;
+        self.duplicates.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates_expire_with_window() {
+        let layout = Layout::DEFAULT;
+        let mut detector = DuplicateDetector::new(layout, Duration::from_millis(100));
+
+        let early = layout.encode(1_000, 3, 0);
+        assert!(!detector.observe(early));
+        assert!(!detector.observe(layout.encode(1_050, 3, 0)));
+        assert!(detector.observe(early));
+        assert_eq!(detector.len(), 2);
+
+        // Moving the window past the first ID forgets it
+        assert!(!detector.observe(layout.encode(1_101, 4, 0)));
+        assert!(!detector.observe(early));
+        assert_eq!(detector.len(), 2);
+
+        assert!(detector.observe(layout.encode(1_101, 4, 0)));
+        assert_eq!(detector.duplicate_count(), 2);
+        assert_eq!(detector.duplicates_by_shard(), vec![(3, 1), (4, 1)]);
+
+        detector.reset();
+        assert!(detector.is_empty());
+        assert_eq!(detector.duplicate_count(), 0);
+    }
+
+    #[test]
+    fn test_out_of_order_ids_expire() {
+        let layout = Layout::DEFAULT;
+        let mut detector = DuplicateDetector::new(layout, Duration::from_millis(100));
+
+        let late = layout.encode(1_050, 3, 0);
+        let early = layout.encode(1_000, 3, 0);
+        assert!(!detector.observe(late));
+        assert!(!detector.observe(early));
+        assert!(detector.observe(early));
+
+        // The earlier ID expires even though it arrived second
+        assert!(!detector.observe(layout.encode(1_120, 3, 0)));
+        assert_eq!(detector.len(), 2);
+        assert!(!detector.observe(early));
+        assert!(detector.observe(late));
+
+        // IDs already outside the window are not remembered
+        assert!(!detector.observe(layout.encode(900, 3, 0)));
+        assert_eq!(detector.len(), 2);
+    }
+}
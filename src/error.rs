@@ -0,0 +1,49 @@
+use std::fmt;
+
+/// Errors returned when an ID cannot be generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BanuidError {
+    /// The clock reads earlier than the last issued timestamp.
+    ClockMovedBackwards { by_ms: u64 },
+    /// The wall clock stepped forward under [`ForwardStepPolicy::Error`](crate::ForwardStepPolicy::Error).
+    ClockJumpedForward { by_ms: u64 },
+    /// The clock reads earlier than the layout's epoch, so no timestamp can be encoded.
+    ClockBeforeEpoch { by_ms: u64 },
+    /// The timestamp no longer fits the layout's timestamp bits.
+    TimestampOverflow,
+    /// The generator state could not be saved.
+    StatePersistence(std::io::ErrorKind),
+    /// Every sequence number in the current tick is taken and the clock is
+    /// not advancing to the next one.
+    SequenceExhausted,
+    /// The OS random number generator failed, so a random sequence could not
+    /// be drawn.
+    EntropyUnavailable,
+}
+
+impl fmt::Display for BanuidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BanuidError::ClockMovedBackwards { by_ms } => {
+                write!(f, "clock moved backwards by {} ms", by_ms)
+            }
+            BanuidError::ClockJumpedForward { by_ms } => {
+                write!(f, "clock jumped forward by {} ms", by_ms)
+            }
+            BanuidError::ClockBeforeEpoch { by_ms } => {
+                write!(f, "clock is {} ms earlier than the layout epoch", by_ms)
+            }
+            BanuidError::TimestampOverflow => {
+                write!(f, "timestamp exceeds the layout's timestamp bits")
+            }
+            BanuidError::StatePersistence(kind) => {
+                write!(f, "failed to save generator state: {}", kind)
+            }
+            BanuidError::SequenceExhausted => write!(f, "sequence exhausted for current tick"),
+            BanuidError::EntropyUnavailable => write!(f, "OS random number generator failed"),
+        }
+    }
+}
+
+impl std::error::Error for BanuidError {}
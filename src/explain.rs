@@ -0,0 +1,130 @@
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+/// Everything an ID encodes, for admin tooling and debug endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdInfo {
+    pub id: u64,
+    /// Creation time in RFC 3339 with millisecond precision, e.g. `2024-01-01T00:00:00.000Z`.
+    pub created_at: String,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// The timestamp field as stored: ticks since the layout's epoch.
+    pub raw_timestamp: u64,
+    pub shard_id: u16,
+    pub datacenter_id: u16,
+    pub worker_id: u16,
+    pub sequence: u16,
+    pub version: u8,
+    pub expiry_class: u8,
+    pub checksum_valid: bool,
+}
+
+impl Layout {
+    /// Decode every field of `id`, with its creation time formatted for humans.
+    pub fn explain(&self, id: u64) -> IdInfo {
+        let timestamp_ms = self.extract_timestamp(id);
+        IdInfo {
+            id,
+            created_at: rfc3339_millis(timestamp_ms),
+            timestamp_ms,
+            raw_timestamp: self.extract_raw_timestamp(id),
+            shard_id: self.extract_shard_id(id),
+            datacenter_id: self.extract_datacenter(id),
+            worker_id: self.extract_worker(id),
+            sequence: self.extract_sequence(id),
+            version: self.extract_version(id),
+            expiry_class: self.extract_expiry_class(id),
+            checksum_valid: self.verify_checksum(id),
+        }
+    }
+}
+
+impl IdGenerator {
+    /// Decode every field of an ID minted with the default layout.
+    pub fn explain(id: u64) -> IdInfo {
+        Layout::DEFAULT.explain(id)
+    }
+}
+
+/// Format milliseconds since the Unix epoch as an RFC 3339 UTC timestamp.
+pub(crate) fn rfc3339_millis(timestamp_ms: u64) -> String {
+    let secs = timestamp_ms / 1_000;
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let seconds_of_day = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        timestamp_ms % 1_000
+    )
+}
+
+// Howard Hinnant's days-to-civil algorithm for the proleptic Gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc3339_millis() {
+        assert_eq!(rfc3339_millis(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            rfc3339_millis(crate::CUSTOM_EPOCH),
+            "2024-01-01T00:00:00.000Z"
+        );
+        assert_eq!(
+            rfc3339_millis(1_709_210_096_789),
+            "2024-02-29T12:34:56.789Z"
+        );
+        assert_eq!(
+            rfc3339_millis(4_102_444_799_999),
+            "2099-12-31T23:59:59.999Z"
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let layout = Layout::SNOWFLAKE;
+        let id = layout.encode(1_000, layout.shard_id_from(3, 9) as u64, 42);
+        let info = layout.explain(id);
+
+        assert_eq!(info.id, id);
+        assert_eq!(info.timestamp_ms, crate::presets::TWITTER.epoch + 1_000);
+        assert_eq!(info.created_at, "2010-11-04T01:42:55.657Z");
+        assert_eq!(info.raw_timestamp, 1_000);
+        assert_eq!((info.datacenter_id, info.worker_id), (3, 9));
+        assert_eq!(info.sequence, 42);
+        assert!(info.checksum_valid);
+        assert_eq!(
+            IdGenerator::explain(5).created_at,
+            "2024-01-01T00:00:00.000Z"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_explain_serde() {
+        let info = IdGenerator::explain(IdGenerator::with_shard_id(1).next_id());
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(json.contains("\"created_at\":\""));
+        assert_eq!(serde_json::from_str::<IdInfo>(&json).unwrap(), info);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::crockford::{
+    decode_crockford, decode_crockford_with_check, encode_crockford, encode_crockford_with_check,
+};
+use crate::id::ParseIdError;
+
+/// How to render IDs for people, on top of Crockford Base32.
+///
+/// The defaults give the plain 13-character form of
+/// [`encode_crockford`](crate::encode_crockford); each option adjusts it.
+///
+/// ```
+/// use banuid::{format_with, parse_with, FormatOptions};
+///
+/// let opts = FormatOptions::new().trim_zeros(true).group(4, '-').check_symbol(true);
+/// let text = format_with(8215838645664, &opts);
+/// assert_eq!(text, "7F3K-29QD-02");
+/// assert_eq!(parse_with("7f3k-29qd-02", &opts), Ok(8215838645664));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    prefix: &'static str,
+    group_size: usize,
+    separator: char,
+    lowercase: bool,
+    check_symbol: bool,
+    trim_zeros: bool,
+}
+
+impl FormatOptions {
+    pub fn new() -> Self {
+        FormatOptions {
+            prefix: "",
+            group_size: 0,
+            separator: '-',
+            lowercase: false,
+            check_symbol: false,
+            trim_zeros: false,
+        }
+    }
+
+    /// Text written before the encoded ID, e.g. `"INV-"`.
+    pub fn prefix(mut self, prefix: &'static str) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Split the characters into groups of `size` from the left, joined by
+    /// `separator`. A size of 0 disables grouping.
+    pub fn group(mut self, size: usize, separator: char) -> Self {
+        self.group_size = size;
+        self.separator = separator;
+        self
+    }
+
+    /// Use lowercase letters instead of uppercase.
+    pub fn lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Append a check symbol that catches typos, see
+    /// [`encode_crockford_with_check`](crate::encode_crockford_with_check).
+    pub fn check_symbol(mut self, check_symbol: bool) -> Self {
+        self.check_symbol = check_symbol;
+        self
+    }
+
+    /// Drop leading zeros instead of padding to 13 characters. Trimmed IDs
+    /// no longer sort as text.
+    pub fn trim_zeros(mut self, trim_zeros: bool) -> Self {
+        self.trim_zeros = trim_zeros;
+        self
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Render `id` as configured by `opts`.
+pub fn format_with(id: u64, opts: &FormatOptions) -> String {
+    let encoded = if opts.check_symbol {
+        encode_crockford_with_check(id)
+    } else {
+        encode_crockford(id)
+    };
+    // Keep at least one digit of the 13
+    let skip = if opts.trim_zeros {
+        encoded[..12].bytes().take_while(|&b| b == b'0').count()
+    } else {
+        0
+    };
+
+    let mut out = String::with_capacity(opts.prefix.len() + encoded.len() * 2);
+    out.push_str(opts.prefix);
+    for (i, c) in encoded[skip..].chars().enumerate() {
+        if opts.group_size > 0 && i > 0 && i % opts.group_size == 0 {
+            out.push(opts.separator);
+        }
+        out.push(if opts.lowercase {
+            c.to_ascii_lowercase()
+        } else {
+            c
+        });
+    }
+    out
+}
+
+/// Parse text written by [`format_with`] with the same options, ignoring case.
+pub fn parse_with(s: &str, opts: &FormatOptions) -> Result<u64, ParseIdError> {
+    let rest = s
+        .strip_prefix(opts.prefix)
+        .ok_or(ParseIdError::MissingPrefix {
+            expected: opts.prefix,
+        })?;
+    let digits: String = rest.chars().filter(|&c| c != opts.separator).collect();
+    if opts.check_symbol {
+        decode_crockford_with_check(&digits)
+    } else {
+        decode_crockford(&digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_options() {
+        let id = 1234567890123456789;
+        assert_eq!(format_with(id, &FormatOptions::new()), "128GGYHYYK08N");
+
+        let opts = FormatOptions::new()
+            .prefix("INV-")
+            .group(3, ' ')
+            .lowercase(true);
+        assert_eq!(format_with(id, &opts), "INV-128 ggy hyy k08 n");
+        assert_eq!(parse_with("INV-128 GGY HYY K08 N", &opts), Ok(id));
+        assert_eq!(
+            parse_with("128 ggy hyy k08 n", &opts),
+            Err(ParseIdError::MissingPrefix { expected: "INV-" })
+        );
+
+        let opts = FormatOptions::new().trim_zeros(true).check_symbol(true);
+        assert_eq!(format_with(0, &opts), "00");
+        assert_eq!(format_with(36, &opts), "14U");
+        assert_eq!(parse_with("14U", &opts), Ok(36));
+        assert_eq!(
+            parse_with("15U", &opts),
+            Err(ParseIdError::CheckSymbolMismatch)
+        );
+    }
+}
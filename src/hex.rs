@@ -0,0 +1,93 @@
+use std::fmt;
+
+use crate::id::{Id, ParseIdError};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Characters needed for any `u64`.
+const HEX_LEN: usize = 16;
+
+/// Encode `id` as 16 lowercase hex characters, zero-padded so that encoded
+/// IDs sort in the same order as the IDs themselves.
+///
+/// ```
+/// let encoded = banuid::encode_hex(1234567890123456789);
+/// assert_eq!(encoded, "112210f47de98115");
+/// assert_eq!(banuid::decode_hex("112210F47DE98115"), Ok(1234567890123456789));
+/// ```
+pub fn encode_hex(id: u64) -> String {
+    format!("{:016x}", id)
+}
+
+/// Write [`encode_hex`]'s 16 characters to the start of `buf` without
+/// allocating, returning the number of bytes written.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than 16 bytes.
+pub fn encode_hex_into(id: u64, buf: &mut [u8]) -> usize {
+    let mut value = id;
+    for slot in buf[..HEX_LEN].iter_mut().rev() {
+        *slot = HEX_DIGITS[(value & 0xF) as usize];
+        value >>= 4;
+    }
+    HEX_LEN
+}
+
+/// Decode exactly 16 hex characters of either case, without a `0x` prefix.
+pub fn decode_hex(s: &str) -> Result<u64, ParseIdError> {
+    if s.len() != HEX_LEN {
+        return Err(ParseIdError::InvalidLength(s.len()));
+    }
+    s.chars().try_fold(0u64, |value, c| {
+        let digit = c.to_digit(16).ok_or(ParseIdError::InvalidChar(c))?;
+        Ok((value << 4) | digit as u64)
+    })
+}
+
+impl<T> Id<T> {
+    /// The ID as 16 lowercase hex characters, see [`encode_hex`].
+    pub fn to_hex(&self) -> String {
+        encode_hex(self.as_u64())
+    }
+}
+
+/// Formats like `u64`, so `{:016x}` gives the fixed-width form.
+impl<T> fmt::LowerHex for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.as_u64(), f)
+    }
+}
+
+/// Formats like `u64`, so `{:016X}` gives the fixed-width form.
+impl<T> fmt::UpperHex for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.as_u64(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_round_trip() {
+        for id in [0, 1, 15, 16, 1 << 40, u64::MAX - 1, u64::MAX] {
+            assert_eq!(encode_hex(id).len(), HEX_LEN);
+            assert_eq!(decode_hex(&encode_hex(id)), Ok(id));
+        }
+        assert_eq!(decode_hex("fff"), Err(ParseIdError::InvalidLength(3)));
+
+        let mut buf = [b' '; 20];
+        assert_eq!(encode_hex_into(0x1234abcd, &mut buf), HEX_LEN);
+        assert_eq!(&buf, b"000000001234abcd    ");
+        assert_eq!(
+            decode_hex("+00000000000000f"),
+            Err(ParseIdError::InvalidChar('+'))
+        );
+
+        let id: Id = Id::from_u64(0xabc).unwrap();
+        assert_eq!(id.to_hex(), "0000000000000abc");
+        assert_eq!(format!("{:x} {:X} {:#06x}", id, id, id), "abc ABC 0x0abc");
+    }
+}
@@ -0,0 +1,216 @@
+#[cfg(feature = "fd-lock")]
+use std::fs::{File, OpenOptions};
+use std::io;
+#[cfg(feature = "fd-lock")]
+use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(feature = "fd-lock")]
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// Source of "hi" blocks for a [`HiLoGenerator`].
+///
+/// Each call must return a value never handed out before, typically backed
+/// by a database sequence. Closures returning `io::Result<u64>` implement it.
+pub trait HiAllocator: Send + Sync {
+    fn next_hi(&self) -> io::Result<u64>;
+}
+
+impl<F> HiAllocator for F
+where
+    F: Fn() -> io::Result<u64> + Send + Sync,
+{
+    fn next_hi(&self) -> io::Result<u64> {
+        self()
+    }
+}
+
+/// Process-local allocator counting up from a starting value.
+#[derive(Debug, Default)]
+pub struct InMemoryHiAllocator {
+    next: AtomicU64,
+}
+
+impl InMemoryHiAllocator {
+    pub fn new(start: u64) -> Self {
+        InMemoryHiAllocator {
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+impl HiAllocator for InMemoryHiAllocator {
+    fn next_hi(&self) -> io::Result<u64> {
+        self.next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |hi| hi.checked_add(1))
+            .map_err(|_| exhausted())
+    }
+}
+
+/// Allocator persisting the next hi value in a file, locked while it is
+/// updated so several processes on one host can share it.
+#[cfg(feature = "fd-lock")]
+#[derive(Debug, Clone)]
+pub struct FileHiAllocator {
+    path: PathBuf,
+}
+
+#[cfg(feature = "fd-lock")]
+impl FileHiAllocator {
+    /// A missing or empty file starts allocation at 0.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileHiAllocator { path: path.into() }
+    }
+}
+
+#[cfg(feature = "fd-lock")]
+impl HiAllocator for FileHiAllocator {
+    fn next_hi(&self) -> io::Result<u64> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        let mut lock = fd_lock::RwLock::new(file);
+        let mut guard = lock.write()?;
+        advance(&mut guard)
+    }
+}
+
+#[cfg(feature = "fd-lock")]
+fn advance(file: &mut File) -> io::Result<u64> {
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    let hi: u64 = match contents.trim() {
+        "" => 0,
+        value => value
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+    };
+
+    let next = hi.checked_add(1).ok_or_else(exhausted)?;
+
+    file.seek(SeekFrom::Start(0))?;
+    file.set_len(0)?;
+    write!(file, "{}", next)?;
+    file.sync_all()?;
+    Ok(hi)
+}
+
+// The last hi value is never handed out, as the next one can't be recorded
+fn exhausted() -> io::Error {
+    io::Error::other("hi allocator exhausted")
+}
+
+struct HiLoState {
+    hi: u64,
+    next_lo: u64,
+}
+
+/// Generates `hi * block_size + lo` IDs, fetching a fresh hi block from an
+/// allocator only once every `block_size` IDs.
+///
+/// Unlike [`IdGenerator`](crate::IdGenerator) these IDs are not time ordered;
+/// they are dense and coordinated through whatever backs the allocator.
+pub struct HiLoGenerator {
+    allocator: Box<dyn HiAllocator>,
+    block_size: u64,
+    state: Mutex<Option<HiLoState>>,
+}
+
+impl HiLoGenerator {
+    /// # Panics
+    ///
+    /// Panics if `block_size` is 0.
+    pub fn new(allocator: impl HiAllocator + 'static, block_size: u64) -> Self {
+        assert!(block_size > 0, "block size must be positive");
+        HiLoGenerator {
+            allocator: Box::new(allocator),
+            block_size,
+            state: Mutex::new(None),
+        }
+    }
+
+    pub fn block_size(&self) -> u64 {
+        self.block_size
+    }
+
+    /// Next ID, calling the allocator when the current block is used up.
+    pub fn next_id(&self) -> io::Result<u64> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if !matches!(&*state, Some(current) if current.next_lo < self.block_size) {
+            let hi = self.allocator.next_hi()?;
+            *state = Some(HiLoState { hi, next_lo: 0 });
+        }
+
+        let current = state.as_mut().expect("block allocated above");
+        let lo = current.next_lo;
+        current.next_lo += 1;
+        current
+            .hi
+            .checked_mul(self.block_size)
+            .and_then(|base| base.checked_add(lo))
+            .ok_or_else(|| io::Error::other("hi/lo ID exceeds u64 range"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_hilo_blocks() {
+        let calls = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&calls);
+        let generator =
+            HiLoGenerator::new(move || Ok(10 + counter.fetch_add(1, Ordering::SeqCst)), 100);
+
+        let ids: Vec<u64> = (0..250).map(|_| generator.next_id().unwrap()).collect();
+        assert_eq!(ids[0], 1_000);
+        assert_eq!(ids[99], 1_099);
+        assert_eq!(ids[100], 1_100);
+        assert_eq!(ids[249], 1_249);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_hilo_allocator_errors_propagate() {
+        let generator = HiLoGenerator::new(|| Err(io::Error::other("db down")), 10);
+        assert!(generator.next_id().is_err());
+
+        let generator = HiLoGenerator::new(InMemoryHiAllocator::new(u64::MAX), 10);
+        assert!(generator.next_id().is_err());
+    }
+
+    #[cfg(feature = "fd-lock")]
+    #[test]
+    fn test_file_allocator_persists() {
+        let path = std::env::temp_dir().join(format!("banuid-hilo-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let allocator = FileHiAllocator::new(&path);
+        assert_eq!(allocator.next_hi().unwrap(), 0);
+        assert_eq!(allocator.next_hi().unwrap(), 1);
+        assert_eq!(FileHiAllocator::new(&path).next_hi().unwrap(), 2);
+
+        std::fs::write(&path, u64::MAX.to_string()).unwrap();
+        assert!(allocator.next_hi().is_err());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            u64::MAX.to_string()
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_in_memory_allocator_exhausts() {
+        let allocator = InMemoryHiAllocator::new(u64::MAX - 1);
+        assert_eq!(allocator.next_hi().unwrap(), u64::MAX - 1);
+        assert!(allocator.next_hi().is_err());
+        assert!(allocator.next_hi().is_err());
+    }
+}
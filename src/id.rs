@@ -0,0 +1,396 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::num::{NonZeroU64, ParseIntError};
+use std::str::FromStr;
+
+use crate::base62;
+use crate::decode::IdComponents;
+use crate::layout::Layout;
+use crate::validate::InvalidId;
+use crate::IdGenerator;
+
+/// A 64-bit ID, kept distinct from counts, sizes and other bare integers.
+///
+/// Accessors decode with [`Layout::DEFAULT`]; use [`Layout`] methods on
+/// [`as_u64`](Self::as_u64) for IDs minted under a custom layout.
+///
+/// The marker `T` names the entity an ID belongs to, so an `Id<User>` cannot
+/// be passed where an `Id<Order>` is expected. It is zero-sized and never
+/// constructed; plain `Id` uses the `()` marker.
+///
+/// IDs are never zero, so `Option<Id>` is as small as a `u64` and 0 stays
+/// free as a sentinel.
+///
+/// ```
+/// use banuid::{Id, IdGenerator};
+///
+/// struct User;
+///
+/// let generator = IdGenerator::with_shard_id(42);
+/// let id: Id = generator.next_typed_id();
+/// assert_eq!(id.shard(), 42);
+/// assert_eq!(id.to_string().parse::<Id>(), Ok(id));
+///
+/// let user: Id<User> = generator.next_typed_id();
+/// assert!(user.as_u64() > id.as_u64());
+/// assert_eq!(Id::<User>::from_u64(0), None);
+/// ```
+pub struct Id<T = ()>(NonZeroU64, PhantomData<fn() -> T>);
+
+impl<T> Id<T> {
+    pub const fn new(value: NonZeroU64) -> Self {
+        Id(value, PhantomData)
+    }
+
+    /// `None` if `value` is zero.
+    pub const fn from_u64(value: u64) -> Option<Self> {
+        match NonZeroU64::new(value) {
+            Some(value) => Some(Id::new(value)),
+            None => None,
+        }
+    }
+
+    /// Reinterpret the ID as belonging to another entity type.
+    pub const fn cast<U>(self) -> Id<U> {
+        Id(self.0, PhantomData)
+    }
+
+    pub const fn as_u64(&self) -> u64 {
+        self.0.get()
+    }
+
+    pub const fn as_non_zero(&self) -> NonZeroU64 {
+        self.0
+    }
+
+    /// The ID's bits as an `i64`, for signed 64-bit columns.
+    ///
+    /// Negative for IDs with the top bit set, which
+    /// [`Layout::new_i64_safe`] layouts never mint.
+    pub const fn as_i64(&self) -> i64 {
+        self.as_u64() as i64
+    }
+
+    /// Read an ID stored with [`as_i64`](Self::as_i64), `None` if `value` is zero.
+    pub const fn from_i64(value: i64) -> Option<Self> {
+        Self::from_u64(value as u64)
+    }
+
+    /// The ID as big-endian bytes, so byte-wise comparison (as in RocksDB or
+    /// FoundationDB keys) orders IDs the same way as the integers.
+    pub const fn to_bytes(&self) -> [u8; 8] {
+        self.as_u64().to_be_bytes()
+    }
+
+    /// Read an ID written by [`to_bytes`](Self::to_bytes), `None` if all bytes are zero.
+    pub const fn from_bytes(bytes: [u8; 8]) -> Option<Self> {
+        Self::from_u64(u64::from_be_bytes(bytes))
+    }
+
+    /// Milliseconds since the Unix epoch at which the ID was created
+    pub const fn timestamp(&self) -> u64 {
+        Layout::DEFAULT.extract_timestamp(self.as_u64())
+    }
+
+    pub const fn shard(&self) -> u16 {
+        Layout::DEFAULT.extract_shard_id(self.as_u64())
+    }
+
+    pub const fn sequence(&self) -> u16 {
+        Layout::DEFAULT.extract_sequence(self.as_u64())
+    }
+
+    pub const fn components(&self) -> IdComponents {
+        Layout::DEFAULT.decode(self.as_u64())
+    }
+}
+
+// Implemented by hand: derives would require the marker type to implement
+// each trait too
+
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Id").field(&self.0).finish()
+    }
+}
+
+impl<T> From<NonZeroU64> for Id<T> {
+    fn from(value: NonZeroU64) -> Self {
+        Id::new(value)
+    }
+}
+
+impl<T> TryFrom<u64> for Id<T> {
+    type Error = InvalidId;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Id::from_u64(value).ok_or(InvalidId::Zero)
+    }
+}
+
+impl<T> From<Id<T>> for NonZeroU64 {
+    fn from(id: Id<T>) -> Self {
+        id.0
+    }
+}
+
+impl<T> From<Id<T>> for u64 {
+    fn from(id: Id<T>) -> Self {
+        id.as_u64()
+    }
+}
+
+/// Formats the ID as a decimal integer, like the `u64` it wraps
+impl<T> fmt::Display for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Parses decimal, `0x`-prefixed hexadecimal or base62 IDs, detecting the
+/// format.
+///
+/// Base62 IDs are always written as 11 characters, so all-digit strings of
+/// any other length are read as decimal and `0x` strings as hexadecimal. At
+/// 11 characters such strings are also valid base62 and are rejected as
+/// [`ParseIdError::Ambiguous`].
+impl<T> FromStr for Id<T> {
+    type Err = ParseIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"));
+        if s.len() == base62::BASE62_LEN && base62::decode(s).is_ok() {
+            if hex.is_some_and(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit())) {
+                return Err(ParseIdError::Ambiguous {
+                    format: "hexadecimal",
+                });
+            }
+            if s.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseIdError::Ambiguous { format: "decimal" });
+            }
+        }
+        let value = if let Some(hex) = hex {
+            u64::from_str_radix(hex, 16).map_err(ParseIdError::InvalidNumber)?
+        } else if !s.bytes().all(|b| b.is_ascii_digit()) {
+            base62::decode(s)?
+        } else {
+            s.parse().map_err(ParseIdError::InvalidNumber)?
+        };
+        Id::from_u64(value).ok_or(ParseIdError::Zero)
+    }
+}
+
+/// Serializes as a plain integer, whatever the marker type. See
+/// [`banuid::serde`](crate::serde) for the string form.
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Id<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.as_u64())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Id<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde::number::deserialize(deserializer)
+    }
+}
+
+/// Error parsing an [`Id`] or an ID type created with [`define_id!`](crate::define_id).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseIdError {
+    /// The string does not start with the type's prefix and separator.
+    MissingPrefix { expected: &'static str },
+    /// The numeric part is not a valid `u64`.
+    InvalidNumber(ParseIntError),
+    /// The character is not a digit in the string's format.
+    InvalidChar(char),
+    /// The value does not fit in 64 bits.
+    Overflow,
+    /// The string has the wrong number of characters for its format.
+    InvalidLength(usize),
+    /// The value is zero, which is never a valid [`Id`].
+    Zero,
+    /// The string is valid both as base62 and in `format`.
+    Ambiguous { format: &'static str },
+    /// The trailing check symbol does not match the decoded value.
+    CheckSymbolMismatch,
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseIdError::MissingPrefix { expected } => {
+                write!(f, "ID must start with \"{}_\"", expected)
+            }
+            ParseIdError::InvalidNumber(err) => write!(f, "invalid ID number: {}", err),
+            ParseIdError::InvalidChar(c) => write!(f, "invalid ID character {:?}", c),
+            ParseIdError::Overflow => write!(f, "ID exceeds 64 bits"),
+            ParseIdError::InvalidLength(len) => {
+                write!(f, "ID has an invalid length of {} characters", len)
+            }
+            ParseIdError::Zero => write!(f, "ID is zero"),
+            ParseIdError::Ambiguous { format } => {
+                write!(
+                    f,
+                    "ID could be {} or base62, use an unambiguous format",
+                    format
+                )
+            }
+            ParseIdError::CheckSymbolMismatch => write!(f, "ID check symbol does not match"),
+        }
+    }
+}
+
+impl std::error::Error for ParseIdError {}
+
+// Parse `{prefix}_{id}`, or a bare number when the prefix is empty
+#[doc(hidden)]
+pub fn parse_prefixed(s: &str, prefix: &'static str) -> Result<u64, ParseIdError> {
+    let digits = if prefix.is_empty() {
+        s
+    } else {
+        s.strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .ok_or(ParseIdError::MissingPrefix { expected: prefix })?
+    };
+    digits.parse().map_err(ParseIdError::InvalidNumber)
+}
+
+impl IdGenerator {
+    /// Generate the next ID as an [`Id`], see [`next_id`](Self::next_id).
+    ///
+    /// Skips the all-zero ID, which a generator with shard 0 could only issue
+    /// in the very first tick after its epoch.
+    pub fn next_typed_id<T>(&self) -> Id<T> {
+        loop {
+            if let Some(id) = Id::from_u64(self.next_id()) {
+                return id;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_accessors() {
+        let id: Id = Id::try_from(Layout::DEFAULT.encode(1_000, 7, 3)).unwrap();
+        assert_eq!(id.timestamp(), crate::CUSTOM_EPOCH + 1_000);
+        assert_eq!(id.shard(), 7);
+        assert_eq!(id.sequence(), 3);
+        assert_eq!(id.components().shard_id, 7);
+        assert_eq!(u64::from(id), id.as_u64());
+
+        assert_eq!(id.to_string(), id.as_u64().to_string());
+        assert_eq!(id.to_string().parse(), Ok(id));
+        assert!("-1".parse::<Id>().is_err());
+        assert_eq!("0".parse::<Id>(), Err(ParseIdError::Zero));
+        assert_eq!(Id::<()>::try_from(0), Err(InvalidId::Zero));
+        assert_eq!(std::mem::size_of::<Option<Id>>(), 8);
+
+        let later: Id = Id::from_u64(id.as_u64() + 256).unwrap();
+        assert!(id.to_bytes() < later.to_bytes());
+        assert_eq!(Id::from_bytes(id.to_bytes()), Some(id));
+        assert_eq!(Id::<()>::from_bytes([0; 8]), None);
+
+        let generator = IdGenerator::with_shard_id(1);
+        assert!(generator.next_typed_id::<()>() < generator.next_typed_id());
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let parse = |s: &str| s.parse::<Id>().map(|id| id.as_u64());
+        assert_eq!(parse("1234567890123456789"), Ok(1234567890123456789));
+        assert_eq!(parse("0x112210f47de98115"), Ok(1234567890123456789));
+        assert_eq!(parse("0X112210F47DE98115"), Ok(1234567890123456789));
+        assert_eq!(parse("1TCKi1nFuNh"), Ok(1234567890123456789));
+        assert_eq!(
+            parse("12345678901"),
+            Err(ParseIdError::Ambiguous { format: "decimal" })
+        );
+        assert_eq!(
+            parse("0x123456789"),
+            Err(ParseIdError::Ambiguous {
+                format: "hexadecimal"
+            })
+        );
+        assert_eq!(
+            ParseIdError::Ambiguous {
+                format: "hexadecimal"
+            }
+            .to_string(),
+            "ID could be hexadecimal or base62, use an unambiguous format"
+        );
+        // Shorter than a base62 ID, so only decimal
+        assert_eq!(parse("1234567890"), Ok(1234567890));
+        assert_eq!(parse("0x12345678"), Ok(0x12345678));
+        assert_eq!(parse("1TCKi1nFu-h"), Err(ParseIdError::InvalidChar('-')));
+        assert!(matches!(parse("0xg"), Err(ParseIdError::InvalidNumber(_))));
+        assert!(matches!(parse(""), Err(ParseIdError::InvalidNumber(_))));
+    }
+
+    #[test]
+    fn test_typed_id() {
+        // Markers need no trait implementations of their own
+        struct Order;
+
+        let order: Id<Order> = Id::from_u64(42).unwrap();
+        assert_eq!(std::mem::size_of::<Id<Order>>(), 8);
+        let copy = order;
+        assert_eq!(order, copy);
+        assert_eq!(format!("{:?} {}", order, order), "Id(42) 42");
+        assert_eq!(Some(order.cast::<()>()), Id::from_u64(42));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_typed_id_serializes_as_integer() {
+        struct User;
+
+        let id: Id<User> = Id::from_u64(1 << 60).unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, (1u64 << 60).to_string());
+        assert_eq!(serde_json::from_str::<Id<User>>(&json).unwrap(), id);
+        assert!(serde_json::from_str::<Id<User>>("0").is_err());
+    }
+}
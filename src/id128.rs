@@ -0,0 +1,267 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::clock::{SystemClock, TimeSource};
+use crate::error::BanuidError;
+use crate::layout::Layout;
+use crate::CUSTOM_EPOCH;
+
+const TIMESTAMP_BITS: u32 = 48;
+const SHARD_ID_BITS: u32 = 16;
+const SEQUENCE_BITS: u32 = 16;
+const RANDOM_BITS: u32 = 48;
+
+const MAX_TIMESTAMP: u128 = (1 << TIMESTAMP_BITS) - 1;
+const MAX_SEQUENCE: u64 = (1 << SEQUENCE_BITS) - 1;
+const MAX_RANDOM: u128 = (1 << RANDOM_BITS) - 1;
+
+const SEQUENCE_SHIFT: u32 = RANDOM_BITS;
+const SHARD_ID_SHIFT: u32 = SEQUENCE_SHIFT + SEQUENCE_BITS;
+const TIMESTAMP_SHIFT: u32 = SHARD_ID_SHIFT + SHARD_ID_BITS;
+
+/// A 128-bit ID: 48-bit millisecond timestamp, 16-bit shard, 16-bit sequence
+/// and 48 random bits, most significant first.
+///
+/// The timestamp covers ~8,900 years from 2024-01-01 and the shard field
+/// allows 65,536 shards. Like the 64-bit IDs, values sort by creation time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Id128(u128);
+
+impl Id128 {
+    pub const fn from_u128(value: u128) -> Self {
+        Id128(value)
+    }
+
+    pub const fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// Milliseconds since the Unix epoch at which the ID was created
+    pub const fn timestamp(&self) -> u64 {
+        ((self.0 >> TIMESTAMP_SHIFT) & MAX_TIMESTAMP) as u64 + CUSTOM_EPOCH
+    }
+
+    pub const fn shard_id(&self) -> u16 {
+        (self.0 >> SHARD_ID_SHIFT) as u16
+    }
+
+    pub const fn sequence(&self) -> u16 {
+        (self.0 >> SEQUENCE_SHIFT) as u16
+    }
+
+    /// The random tail bits
+    pub const fn random(&self) -> u64 {
+        (self.0 & MAX_RANDOM) as u64
+    }
+}
+
+impl From<u128> for Id128 {
+    fn from(value: u128) -> Self {
+        Id128(value)
+    }
+}
+
+impl From<Id128> for u128 {
+    fn from(id: Id128) -> Self {
+        id.0
+    }
+}
+
+/// Formats the ID as 32 lowercase hex digits, which sort like the IDs themselves
+impl fmt::Display for Id128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+struct State128 {
+    last_timestamp: u64,
+    sequence: u64,
+}
+
+/// Generator for [`Id128`] values.
+pub struct IdGenerator128 {
+    shard_id: u16,
+    clock: Arc<dyn TimeSource>,
+    state: Mutex<State128>,
+}
+
+impl IdGenerator128 {
+    pub fn new() -> Self {
+        Self::with_shard_id(crate::derive_shard_id())
+    }
+
+    pub fn with_shard_id(shard_id: u16) -> Self {
+        IdGenerator128 {
+            shard_id,
+            clock: Arc::new(SystemClock),
+            state: Mutex::new(State128 {
+                last_timestamp: 0,
+                sequence: 0,
+            }),
+        }
+    }
+
+    /// Read time from `clock` instead of the system clock.
+    pub fn with_time_source(mut self, clock: impl TimeSource + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Generate an ID using this instance
+    pub fn generate(&self) -> Id128 {
+        self.next_id()
+    }
+
+    /// Generate the next ID, waiting out clock regressions and exhausted
+    /// milliseconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clock reads before 2024 or past the end of the
+    /// timestamp range.
+    pub fn next_id(&self) -> Id128 {
+        loop {
+            match self.try_next_id() {
+                Ok(id) => return id,
+                Err(BanuidError::ClockMovedBackwards { by_ms }) => {
+                    self.clock.sleep(Duration::from_millis(by_ms));
+                }
+                Err(BanuidError::SequenceExhausted) => {
+                    self.clock.sleep(Duration::from_millis(1));
+                }
+                Err(err) => panic!("{}", err),
+            }
+        }
+    }
+
+    /// Generate the next ID without waiting, failing if the clock is behind
+    /// the last issued timestamp or the current millisecond's sequence is
+    /// used up.
+    pub fn try_next_id(&self) -> Result<Id128, BanuidError> {
+        let now = self.clock.now();
+        // Layout::DEFAULT shares the millisecond unit and epoch
+        let Some(timestamp) = Layout::DEFAULT.checked_ticks_at(now) else {
+            let epoch = Duration::from_millis(CUSTOM_EPOCH);
+            return Err(BanuidError::ClockBeforeEpoch {
+                by_ms: (epoch - now).as_micros().div_ceil(1_000) as u64,
+            });
+        };
+        if timestamp as u128 > MAX_TIMESTAMP {
+            return Err(BanuidError::TimestampOverflow);
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if timestamp < state.last_timestamp {
+            return Err(BanuidError::ClockMovedBackwards {
+                by_ms: state.last_timestamp - timestamp,
+            });
+        }
+        if timestamp == state.last_timestamp {
+            if state.sequence >= MAX_SEQUENCE {
+                return Err(BanuidError::SequenceExhausted);
+            }
+            state.sequence += 1;
+        } else {
+            state.last_timestamp = timestamp;
+            state.sequence = 0;
+        }
+
+        let random = crate::random::random_u64() as u128 & MAX_RANDOM;
+        Ok(Id128(
+            ((timestamp as u128) << TIMESTAMP_SHIFT)
+                | ((self.shard_id as u128) << SHARD_ID_SHIFT)
+                | ((state.sequence as u128) << SEQUENCE_SHIFT)
+                | random,
+        ))
+    }
+
+    pub fn extract_timestamp(id: Id128) -> u64 {
+        id.timestamp()
+    }
+
+    pub fn extract_shard_id(id: Id128) -> u16 {
+        id.shard_id()
+    }
+
+    pub fn extract_sequence(id: Id128) -> u16 {
+        id.sequence()
+    }
+
+    pub fn extract_random(id: Id128) -> u64 {
+        id.random()
+    }
+
+    pub fn shard_id(&self) -> u16 {
+        self.shard_id
+    }
+}
+
+impl Default for IdGenerator128 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockClock;
+
+    #[test]
+    fn test_id128_generation() {
+        let generator = IdGenerator128::with_shard_id(40_000);
+        let id1 = generator.next_id();
+        let id2 = generator.next_id();
+
+        assert!(id2 > id1, "IDs should be time ordered");
+        assert_eq!(IdGenerator128::extract_shard_id(id1), 40_000);
+        assert_eq!(id1.to_string().len(), 32);
+
+        let now = crate::current_time().as_millis() as u64;
+        let timestamp = IdGenerator128::extract_timestamp(id2);
+        assert!(timestamp <= now && timestamp >= now - 1000);
+    }
+
+    #[test]
+    fn test_id128_clock_errors() {
+        let clock = MockClock::new(CUSTOM_EPOCH - 5);
+        let generator = IdGenerator128::with_shard_id(1).with_time_source(clock.clone());
+        assert_eq!(
+            generator.try_next_id(),
+            Err(BanuidError::ClockBeforeEpoch { by_ms: 5 })
+        );
+
+        clock.set(CUSTOM_EPOCH + 100);
+        let id = generator.try_next_id().unwrap();
+        assert_eq!(id.timestamp(), CUSTOM_EPOCH + 100);
+        clock.set(CUSTOM_EPOCH + 90);
+        assert_eq!(
+            generator.try_next_id(),
+            Err(BanuidError::ClockMovedBackwards { by_ms: 10 })
+        );
+        // next_id waits for the clock to catch up
+        let later = generator.next_id();
+        assert!(later > id);
+        assert_eq!(later.timestamp(), CUSTOM_EPOCH + 100);
+    }
+
+    #[test]
+    fn test_id128_field_layout() {
+        let raw = (5u128 << TIMESTAMP_SHIFT)
+            | (7u128 << SHARD_ID_SHIFT)
+            | (9u128 << SEQUENCE_SHIFT)
+            | 0xABCDEF;
+        let id = Id128::from_u128(raw);
+
+        assert_eq!(id.timestamp(), CUSTOM_EPOCH + 5);
+        assert_eq!(id.shard_id(), 7);
+        assert_eq!(id.sequence(), 9);
+        assert_eq!(id.random(), 0xABCDEF);
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use crate::layout::Layout;
+
+/// KSUID timestamps count seconds from 2014-05-13T16:53:20Z.
+const KSUID_EPOCH_SECS: u64 = 1_400_000_000;
+
+/// A KSUID in its 20-byte binary form: a big-endian 32-bit timestamp in
+/// seconds since the KSUID epoch, followed by 16 payload bytes.
+///
+/// KSUIDs built from banuid IDs carry the whole ID at the start of the
+/// payload, so they convert back losslessly; [`Layout::id_from_ksuid`] maps
+/// KSUIDs minted elsewhere into a layout when migrating.
+///
+/// ```
+/// use banuid::{IdGenerator, Ksuid};
+///
+/// let id = IdGenerator::with_shard_id(9).next_id();
+/// let ksuid = Ksuid::from_id(id);
+/// assert_eq!(ksuid.timestamp_secs(), IdGenerator::extract_timestamp(id) / 1000);
+/// assert_eq!(Ksuid::from_bytes(ksuid.to_bytes()).to_id(), id);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ksuid([u8; 20]);
+
+impl Ksuid {
+    pub const fn from_bytes(bytes: [u8; 20]) -> Self {
+        Ksuid(bytes)
+    }
+
+    pub const fn to_bytes(&self) -> [u8; 20] {
+        self.0
+    }
+
+    /// Seconds since the Unix epoch
+    pub const fn timestamp_secs(&self) -> u64 {
+        let secs = u32::from_be_bytes([self.0[0], self.0[1], self.0[2], self.0[3]]);
+        secs as u64 + KSUID_EPOCH_SECS
+    }
+
+    /// The 16 payload bytes
+    pub fn payload(&self) -> [u8; 16] {
+        let mut payload = [0; 16];
+        payload.copy_from_slice(&self.0[4..]);
+        payload
+    }
+
+    /// Convert an ID from the default layout, keeping its timestamp to the second.
+    pub fn from_id(id: u64) -> Self {
+        Layout::DEFAULT.to_ksuid(id)
+    }
+
+    /// Recover the ID this KSUID was built from, read from the start of the payload.
+    pub fn to_id(&self) -> u64 {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.0[4..12]);
+        u64::from_be_bytes(bytes)
+    }
+}
+
+impl Layout {
+    /// Convert an ID minted with this layout to a KSUID.
+    ///
+    /// The payload is the ID's big-endian bytes followed by zeros, so the same
+    /// ID always yields the same KSUID and KSUIDs sort like their IDs.
+    pub fn to_ksuid(&self, id: u64) -> Ksuid {
+        let secs = (self.extract_timestamp(id) / 1000)
+            .saturating_sub(KSUID_EPOCH_SECS)
+            .min(u32::MAX as u64);
+        let mut bytes = [0; 20];
+        bytes[..4].copy_from_slice(&(secs as u32).to_be_bytes());
+        bytes[4..12].copy_from_slice(&id.to_be_bytes());
+        Ksuid(bytes)
+    }
+
+    /// Map a KSUID minted elsewhere to an ID in this layout.
+    ///
+    /// The timestamp is the start of the KSUID's second, and the shard and
+    /// sequence fields take the low bits of the first and second halves of the
+    /// payload. This is lossy: distinct KSUIDs can map to the same ID. Returns
+    /// `None` if the KSUID's time lies outside the layout's range.
+    pub fn id_from_ksuid(&self, ksuid: &Ksuid) -> Option<u64> {
+        let ticks = self.checked_ticks_at(Duration::from_secs(ksuid.timestamp_secs()))?;
+        if ticks > self.max_timestamp() {
+            return None;
+        }
+        let payload = ksuid.payload();
+        let (high, low) = payload.split_at(8);
+        Some(self.encode(
+            ticks,
+            u64::from_be_bytes(high.try_into().unwrap()),
+            u64::from_be_bytes(low.try_into().unwrap()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IdGenerator;
+
+    #[test]
+    fn test_ksuid_id_round_trip() {
+        let generator = IdGenerator::with_shard_id(3);
+        let ids: Vec<u64> = (0..100).map(|_| generator.next_id()).collect();
+        let ksuids: Vec<Ksuid> = ids.iter().map(|&id| Ksuid::from_id(id)).collect();
+
+        assert!(ksuids.windows(2).all(|pair| pair[0] < pair[1]));
+        for (id, ksuid) in ids.iter().zip(&ksuids) {
+            assert_eq!(ksuid.to_id(), *id);
+            assert_eq!(ksuid.payload()[8..], [0; 8]);
+        }
+    }
+
+    #[test]
+    fn test_id_from_foreign_ksuid() {
+        // 0ujtsYcgvSTl8PAuAdqWYSMnLOv from the KSUID reference implementation
+        let mut bytes = [0; 20];
+        bytes[..4].copy_from_slice(&107_608_047u32.to_be_bytes());
+        bytes[4..].copy_from_slice(&0xB5A1_CD34_B5F9_9D11_18F0_F2D6_2B72_06BCu128.to_be_bytes());
+        let ksuid = Ksuid::from_bytes(bytes);
+        assert_eq!(ksuid.timestamp_secs(), 1_507_608_047);
+
+        // Older than the default epoch
+        assert_eq!(Layout::DEFAULT.id_from_ksuid(&ksuid), None);
+
+        let layout = Layout::DEFAULT.with_epoch(1_500_000_000_000);
+        let id = layout.id_from_ksuid(&ksuid).unwrap();
+        let components = layout.decode(id);
+        assert_eq!(components.timestamp_ms, 1_507_608_047_000);
+        assert_eq!(components.shard_id as u64, 0x9D11 & layout.max_shard_id());
+        assert_eq!(components.sequence as u64, 0x06BC & layout.max_sequence());
+    }
+}
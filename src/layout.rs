@@ -0,0 +1,1011 @@
+use std::time::Duration;
+
+use crate::builder::BuildError;
+use crate::decode::IdComponents;
+
+/// Granularity of the timestamp field.
+///
+/// Coarser units stretch the lifetime of a timestamp field, finer units order
+/// IDs more precisely. The sequence counter resets once per tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TimeUnit {
+    Seconds,
+    TenMillis,
+    #[default]
+    Millis,
+    Micros,
+}
+
+impl TimeUnit {
+    /// Length of one tick in microseconds.
+    pub const fn as_micros(&self) -> u64 {
+        match self {
+            TimeUnit::Seconds => 1_000_000,
+            TimeUnit::TenMillis => 10_000,
+            TimeUnit::Millis => 1_000,
+            TimeUnit::Micros => 1,
+        }
+    }
+
+    /// Length of one tick.
+    pub const fn duration(&self) -> Duration {
+        Duration::from_micros(self.as_micros())
+    }
+}
+
+/// How the current time is rounded to a whole number of ticks.
+///
+/// Only matters for units coarser than the clock, e.g. to match another
+/// implementation that rounds seconds to the nearest instead of truncating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Rounding {
+    /// Truncate to the start of the current tick.
+    #[default]
+    Floor,
+    /// Round to the nearest tick boundary, halves rounding up.
+    Nearest,
+}
+
+/// Order of the shard and sequence fields below the timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FieldOrder {
+    /// `[timestamp | shard | sequence]`, as used by banuid and Snowflake.
+    #[default]
+    ShardFirst,
+    /// `[timestamp | sequence | shard]`, as used by Sonyflake.
+    SequenceFirst,
+}
+
+/// Bit layout of a 64-bit ID: `[timestamp | shard | sequence]`, most significant
+/// first unless a different [`FieldOrder`] is chosen.
+///
+/// The timestamp field stores ticks of the layout's [`TimeUnit`] elapsed since
+/// the layout's epoch. The shard field may be split into a datacenter part
+/// (high bits) and a worker part (low bits). A layout may also reserve a few
+/// version bits above the timestamp, see [`Layout::with_version`], an expiry
+/// class right below it, see [`Layout::with_expiry_class`], and checksum bits
+/// below everything else, see [`Layout::with_checksum`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Layout {
+    epoch: u64,
+    unit: TimeUnit,
+    rounding: Rounding,
+    order: FieldOrder,
+    timestamp_bits: u8,
+    shard_bits: u8,
+    datacenter_bits: u8,
+    sequence_bits: u8,
+    version_bits: u8,
+    version: u8,
+    expiry_class_bits: u8,
+    checksum_bits: u8,
+}
+
+impl Layout {
+    /// The default 41/13/10 layout.
+    pub const DEFAULT: Layout = Layout {
+        epoch: crate::CUSTOM_EPOCH,
+        unit: TimeUnit::Millis,
+        rounding: Rounding::Floor,
+        order: FieldOrder::ShardFirst,
+        timestamp_bits: crate::TIMESTAMP_BITS,
+        shard_bits: crate::SHARD_ID_BITS,
+        datacenter_bits: 0,
+        sequence_bits: crate::SEQUENCE_BITS,
+        version_bits: 0,
+        version: 0,
+        expiry_class_bits: 0,
+        checksum_bits: 0,
+    };
+
+    /// Highest bit count whose values JavaScript numbers represent exactly.
+    pub const JS_SAFE_BITS: u8 = 53;
+
+    /// Create a layout, validating that the fields fit a 64-bit ID.
+    pub const fn new(
+        timestamp_bits: u8,
+        shard_bits: u8,
+        sequence_bits: u8,
+    ) -> Result<Self, BuildError> {
+        let layout = match Self::with_widths(timestamp_bits, shard_bits, sequence_bits) {
+            Ok(layout) => layout,
+            Err(err) => return Err(err),
+        };
+
+        let total = layout.total_bits() as u32;
+        if total != 63 && total != 64 {
+            return Err(BuildError::InvalidTotalBits { total });
+        }
+        Ok(layout)
+    }
+
+    /// Create a layout whose IDs never exceed 2^53, so they survive a
+    /// round-trip through a JavaScript number.
+    pub const fn new_js_safe(
+        timestamp_bits: u8,
+        shard_bits: u8,
+        sequence_bits: u8,
+    ) -> Result<Self, BuildError> {
+        let layout = match Self::with_widths(timestamp_bits, shard_bits, sequence_bits) {
+            Ok(layout) => layout,
+            Err(err) => return Err(err),
+        };
+
+        let total = layout.total_bits() as u32;
+        if total > Self::JS_SAFE_BITS as u32 {
+            return Err(BuildError::NotJsSafe { total });
+        }
+        Ok(layout)
+    }
+
+    /// Create a layout that keeps the top bit clear, so IDs stay positive
+    /// when stored in signed 64-bit columns.
+    pub const fn new_i64_safe(
+        timestamp_bits: u8,
+        shard_bits: u8,
+        sequence_bits: u8,
+    ) -> Result<Self, BuildError> {
+        let layout = match Self::with_widths(timestamp_bits, shard_bits, sequence_bits) {
+            Ok(layout) => layout,
+            Err(err) => return Err(err),
+        };
+
+        let total = layout.total_bits() as u32;
+        if total > 63 {
+            return Err(BuildError::NotI64Safe { total });
+        }
+        Ok(layout)
+    }
+
+    /// Create a layout for 32-bit short IDs, see [`ShortIdGenerator`](crate::ShortIdGenerator).
+    pub const fn new_short(
+        timestamp_bits: u8,
+        shard_bits: u8,
+        sequence_bits: u8,
+    ) -> Result<Self, BuildError> {
+        let layout = match Self::with_widths(timestamp_bits, shard_bits, sequence_bits) {
+            Ok(layout) => layout,
+            Err(err) => return Err(err),
+        };
+
+        let total = layout.total_bits() as u32;
+        if total > 32 {
+            return Err(BuildError::NotU32Safe { total });
+        }
+        Ok(layout)
+    }
+
+    const fn with_widths(
+        timestamp_bits: u8,
+        shard_bits: u8,
+        sequence_bits: u8,
+    ) -> Result<Self, BuildError> {
+        if shard_bits > 16 {
+            return Err(BuildError::FieldTooWide {
+                field: "shard",
+                bits: shard_bits,
+                max: 16,
+            });
+        }
+        if sequence_bits > 16 {
+            return Err(BuildError::FieldTooWide {
+                field: "sequence",
+                bits: sequence_bits,
+                max: 16,
+            });
+        }
+        if timestamp_bits > 64 {
+            return Err(BuildError::FieldTooWide {
+                field: "timestamp",
+                bits: timestamp_bits,
+                max: 64,
+            });
+        }
+
+        Ok(Layout {
+            epoch: crate::CUSTOM_EPOCH,
+            unit: TimeUnit::Millis,
+            rounding: Rounding::Floor,
+            order: FieldOrder::ShardFirst,
+            timestamp_bits,
+            shard_bits,
+            datacenter_bits: 0,
+            sequence_bits,
+            version_bits: 0,
+            version: 0,
+            expiry_class_bits: 0,
+            checksum_bits: 0,
+        })
+    }
+
+    /// Split the shard field, giving its top `datacenter_bits` to a datacenter ID
+    /// and the remainder to a worker ID.
+    pub const fn split_shard(mut self, datacenter_bits: u8) -> Result<Self, BuildError> {
+        if datacenter_bits > self.shard_bits {
+            return Err(BuildError::FieldTooWide {
+                field: "datacenter",
+                bits: datacenter_bits,
+                max: self.shard_bits,
+            });
+        }
+        self.datacenter_bits = datacenter_bits;
+        Ok(self)
+    }
+
+    /// Reserve the top `version_bits` of the timestamp field for a layout version,
+    /// stamped as `version` on every ID.
+    ///
+    /// The timestamp field shrinks by `version_bits`, so the total width is unchanged.
+    /// Calling it again replaces the earlier version and its bits.
+    pub const fn with_version(mut self, version_bits: u8, version: u8) -> Result<Self, BuildError> {
+        // Bits above the timestamp and sequence fields, including any already reserved
+        let available = self.timestamp_bits + self.version_bits;
+        if version_bits > 8 || version_bits >= available {
+            return Err(BuildError::FieldTooWide {
+                field: "version",
+                bits: version_bits,
+                max: if available > 8 {
+                    8
+                } else {
+                    available.saturating_sub(1)
+                },
+            });
+        }
+        if version as u64 > mask(version_bits) {
+            return Err(BuildError::VersionOutOfRange {
+                version,
+                max: mask(version_bits) as u8,
+            });
+        }
+        self.timestamp_bits = available - version_bits;
+        self.version_bits = version_bits;
+        self.version = version;
+        Ok(self)
+    }
+
+    /// Reserve `class_bits` (taken from the worker part of the shard field) for an
+    /// expiry class chosen per ID, e.g. 0 = keep forever, 1 = 7 days, 2 = 30 days.
+    ///
+    /// The class sits right below the timestamp; see [`Layout::set_expiry_class`].
+    pub const fn with_expiry_class(mut self, class_bits: u8) -> Result<Self, BuildError> {
+        let available = self.worker_bits() + self.expiry_class_bits;
+        if class_bits > 8 || class_bits > available {
+            return Err(BuildError::FieldTooWide {
+                field: "expiry class",
+                bits: class_bits,
+                max: if available < 8 { available } else { 8 },
+            });
+        }
+        self.shard_bits = self.shard_bits + self.expiry_class_bits - class_bits;
+        self.expiry_class_bits = class_bits;
+        Ok(self)
+    }
+
+    /// Reserve the low `checksum_bits` of the sequence field for a checksum over
+    /// the rest of the ID, so corrupted IDs fail [`Layout::verify_checksum`].
+    ///
+    /// The sequence field shrinks by `checksum_bits`, lowering per-tick throughput.
+    pub const fn with_checksum(mut self, checksum_bits: u8) -> Result<Self, BuildError> {
+        let available = self.sequence_bits + self.checksum_bits;
+        if checksum_bits > 8 || checksum_bits > available {
+            return Err(BuildError::FieldTooWide {
+                field: "checksum",
+                bits: checksum_bits,
+                max: if available < 8 { available } else { 8 },
+            });
+        }
+        self.sequence_bits = available - checksum_bits;
+        self.checksum_bits = checksum_bits;
+        Ok(self)
+    }
+
+    /// Use a different epoch, in milliseconds since the Unix epoch.
+    pub const fn with_epoch(mut self, epoch: u64) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Use a different timestamp granularity.
+    pub const fn with_unit(mut self, unit: TimeUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Use a different rounding of the current time to ticks.
+    pub const fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Use a different order for the shard and sequence fields.
+    pub const fn with_field_order(mut self, order: FieldOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Epoch in milliseconds since the Unix epoch.
+    pub const fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub const fn unit(&self) -> TimeUnit {
+        self.unit
+    }
+
+    pub const fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    pub const fn field_order(&self) -> FieldOrder {
+        self.order
+    }
+
+    pub const fn timestamp_bits(&self) -> u8 {
+        self.timestamp_bits
+    }
+
+    pub const fn shard_bits(&self) -> u8 {
+        self.shard_bits
+    }
+
+    pub const fn datacenter_bits(&self) -> u8 {
+        self.datacenter_bits
+    }
+
+    pub const fn worker_bits(&self) -> u8 {
+        self.shard_bits - self.datacenter_bits
+    }
+
+    pub const fn sequence_bits(&self) -> u8 {
+        self.sequence_bits
+    }
+
+    /// Number of bits used by all fields together.
+    pub const fn total_bits(&self) -> u8 {
+        self.version_bits
+            + self.timestamp_bits
+            + self.expiry_class_bits
+            + self.shard_bits
+            + self.sequence_bits
+            + self.checksum_bits
+    }
+
+    pub const fn expiry_class_bits(&self) -> u8 {
+        self.expiry_class_bits
+    }
+
+    pub const fn max_expiry_class(&self) -> u8 {
+        mask(self.expiry_class_bits) as u8
+    }
+
+    pub const fn checksum_bits(&self) -> u8 {
+        self.checksum_bits
+    }
+
+    pub const fn version_bits(&self) -> u8 {
+        self.version_bits
+    }
+
+    /// Version stamped on IDs of this layout; 0 when no version bits are reserved.
+    pub const fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Whether every ID in this layout is exactly representable as a JavaScript number.
+    pub const fn is_js_safe(&self) -> bool {
+        self.total_bits() <= Self::JS_SAFE_BITS
+    }
+
+    /// Whether every ID in this layout is positive when cast to `i64`.
+    pub const fn is_i64_safe(&self) -> bool {
+        self.total_bits() <= 63
+    }
+
+    pub const fn max_timestamp(&self) -> u64 {
+        mask(self.timestamp_bits)
+    }
+
+    pub const fn max_shard_id(&self) -> u64 {
+        mask(self.shard_bits)
+    }
+
+    pub const fn max_sequence(&self) -> u64 {
+        mask(self.sequence_bits)
+    }
+
+    pub const fn max_datacenter_id(&self) -> u64 {
+        mask(self.datacenter_bits)
+    }
+
+    pub const fn max_worker_id(&self) -> u64 {
+        mask(self.worker_bits())
+    }
+
+    /// Combine a datacenter and worker ID into a shard ID. Out-of-range values are truncated.
+    pub const fn shard_id_from(&self, datacenter_id: u16, worker_id: u16) -> u16 {
+        (((datacenter_id as u64 & self.max_datacenter_id()) << self.worker_bits())
+            | (worker_id as u64 & self.max_worker_id())) as u16
+    }
+
+    /// Bit offset of the shard field within an ID.
+    pub const fn shard_shift(&self) -> u32 {
+        self.checksum_bits as u32
+            + match self.order {
+                FieldOrder::ShardFirst => self.sequence_bits as u32,
+                FieldOrder::SequenceFirst => 0,
+            }
+    }
+
+    /// Bit offset of the sequence field within an ID.
+    pub const fn sequence_shift(&self) -> u32 {
+        self.checksum_bits as u32
+            + match self.order {
+                FieldOrder::ShardFirst => 0,
+                FieldOrder::SequenceFirst => self.shard_bits as u32,
+            }
+    }
+
+    /// Bit offset of the expiry class field within an ID.
+    pub const fn expiry_class_shift(&self) -> u32 {
+        (self.checksum_bits + self.sequence_bits + self.shard_bits) as u32
+    }
+
+    /// Bit offset of the timestamp field within an ID.
+    pub const fn timestamp_shift(&self) -> u32 {
+        self.expiry_class_shift() + self.expiry_class_bits as u32
+    }
+
+    /// Bit offset of the version field within an ID.
+    pub const fn version_shift(&self) -> u32 {
+        self.timestamp_shift() + self.timestamp_bits as u32
+    }
+
+    /// Bits of an ID holding the timestamp.
+    pub const fn timestamp_mask(&self) -> u64 {
+        field_mask(self.timestamp_bits, self.timestamp_shift())
+    }
+
+    /// Bits of an ID holding the shard ID.
+    pub const fn shard_mask(&self) -> u64 {
+        field_mask(self.shard_bits, self.shard_shift())
+    }
+
+    /// Bits of an ID holding the sequence.
+    pub const fn sequence_mask(&self) -> u64 {
+        field_mask(self.sequence_bits, self.sequence_shift())
+    }
+
+    /// Bits of an ID holding the expiry class.
+    pub const fn expiry_class_mask(&self) -> u64 {
+        field_mask(self.expiry_class_bits, self.expiry_class_shift())
+    }
+
+    /// Bits of an ID holding the version.
+    pub const fn version_mask(&self) -> u64 {
+        field_mask(self.version_bits, self.version_shift())
+    }
+
+    /// Bits of an ID holding the checksum, always the lowest ones.
+    pub const fn checksum_mask(&self) -> u64 {
+        mask(self.checksum_bits)
+    }
+
+    /// Pack the raw fields into an ID. Values wider than their field are truncated.
+    pub const fn encode(&self, timestamp: u64, shard_id: u64, sequence: u64) -> u64 {
+        let version = if self.version_bits == 0 {
+            0
+        } else {
+            (self.version as u64) << self.version_shift()
+        };
+        let id = version
+            | ((timestamp & self.max_timestamp()) << self.timestamp_shift())
+            | ((shard_id & self.max_shard_id()) << self.shard_shift())
+            | ((sequence & self.max_sequence()) << self.sequence_shift());
+        id | self.checksum(id)
+    }
+
+    /// Replace the expiry class of `id`, updating its checksum.
+    /// Classes wider than the field are truncated.
+    pub const fn set_expiry_class(&self, id: u64, class: u8) -> u64 {
+        let field = mask(self.expiry_class_bits) << self.expiry_class_shift();
+        let id = (id & !field & !mask(self.checksum_bits))
+            | ((class as u64) << self.expiry_class_shift()) & field;
+        id | self.checksum(id)
+    }
+
+    /// Expiry class of `id`; 0 when the layout reserves no class bits.
+    pub const fn extract_expiry_class(&self, id: u64) -> u8 {
+        ((id >> self.expiry_class_shift()) & mask(self.expiry_class_bits)) as u8
+    }
+
+    /// Whether the checksum bits of `id` match the rest of the ID.
+    /// Always true for layouts without checksum bits.
+    pub const fn verify_checksum(&self, id: u64) -> bool {
+        id & mask(self.checksum_bits) == self.checksum(id)
+    }
+
+    // XOR-fold of the bits above the checksum field, each chunk rotated by its
+    // position. Any single flipped bit changes exactly one checksum bit.
+    const fn checksum(&self, id: u64) -> u64 {
+        let bits = self.checksum_bits as u64;
+        if bits == 0 {
+            return 0;
+        }
+
+        let mut payload = id >> bits;
+        let mut folded = 0;
+        let mut index = 0;
+        while payload != 0 {
+            let chunk = payload & mask(self.checksum_bits);
+            let rotate = index % bits;
+            folded ^= ((chunk << rotate) | (chunk >> (bits - rotate))) & mask(self.checksum_bits);
+            payload >>= bits;
+            index += 1;
+        }
+        folded
+    }
+
+    /// Milliseconds since the Unix epoch at which `id` was created.
+    ///
+    /// With [`TimeUnit::Micros`] the sub-millisecond part is truncated.
+    pub const fn extract_timestamp(&self, id: u64) -> u64 {
+        let micros = self.extract_raw_timestamp(id) as u128 * self.unit.as_micros() as u128;
+        (micros / 1_000) as u64 + self.epoch
+    }
+
+    /// Split `id` into its timestamp, shard and sequence.
+    pub const fn decode(&self, id: u64) -> IdComponents {
+        IdComponents {
+            timestamp_ms: self.extract_timestamp(id),
+            shard_id: self.extract_shard_id(id),
+            sequence: self.extract_sequence(id),
+        }
+    }
+
+    /// Plain description of this layout.
+    pub const fn spec(&self) -> LayoutSpec {
+        LayoutSpec {
+            epoch: self.epoch,
+            timestamp_bits: self.timestamp_bits,
+            shard_bits: self.shard_bits,
+            sequence_bits: self.sequence_bits,
+            unit: self.unit,
+            field_order: self.order,
+            rounding: self.rounding,
+            datacenter_bits: self.datacenter_bits,
+            version_bits: self.version_bits,
+            version: self.version,
+            expiry_class_bits: self.expiry_class_bits,
+            checksum_bits: self.checksum_bits,
+        }
+    }
+
+    /// Version field of `id`; 0 when the layout reserves no version bits.
+    pub const fn extract_version(&self, id: u64) -> u8 {
+        if self.version_bits == 0 {
+            0
+        } else {
+            ((id >> self.version_shift()) & mask(self.version_bits)) as u8
+        }
+    }
+
+    /// The timestamp field as stored: ticks since the layout's epoch.
+    pub const fn extract_raw_timestamp(&self, id: u64) -> u64 {
+        (id >> self.timestamp_shift()) & self.max_timestamp()
+    }
+
+    /// Ticks elapsed between the layout's epoch and `now` (time since the Unix
+    /// epoch), or `None` if `now` is earlier than the epoch.
+    pub(crate) fn checked_ticks_at(&self, now: Duration) -> Option<u64> {
+        let elapsed = (now.as_micros() + self.rounding_offset() as u128)
+            .checked_sub(self.epoch as u128 * 1_000)?;
+        Some((elapsed / self.unit.as_micros() as u128).min(u64::MAX as u128) as u64)
+    }
+
+    // Shift applied before truncating, so that truncation rounds as configured
+    const fn rounding_offset(&self) -> u64 {
+        match self.rounding {
+            Rounding::Floor => 0,
+            Rounding::Nearest => self.unit.as_micros() / 2,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn ticks_at(&self, now: Duration) -> u64 {
+        self.checked_ticks_at(now).unwrap()
+    }
+
+    /// Time remaining until the tick containing `now` ends.
+    pub(crate) fn until_next_tick(&self, now: Duration) -> Duration {
+        let unit = self.unit.as_micros();
+        let elapsed = now.as_micros() as u64 + self.rounding_offset() - self.epoch * 1_000;
+        Duration::from_micros(unit - elapsed % unit)
+    }
+
+    pub const fn extract_shard_id(&self, id: u64) -> u16 {
+        ((id >> self.shard_shift()) & self.max_shard_id()) as u16
+    }
+
+    /// Datacenter part of the shard field; always 0 when the shard is not split.
+    pub const fn extract_datacenter(&self, id: u64) -> u16 {
+        (self.extract_shard_id(id) as u64 >> self.worker_bits()) as u16
+    }
+
+    /// Worker part of the shard field; the whole shard ID when the shard is not split.
+    pub const fn extract_worker(&self, id: u64) -> u16 {
+        (self.extract_shard_id(id) as u64 & self.max_worker_id()) as u16
+    }
+
+    pub const fn extract_sequence(&self, id: u64) -> u16 {
+        ((id >> self.sequence_shift()) & self.max_sequence()) as u16
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::DEFAULT
+    }
+}
+
+/// Plain description of a [`Layout`], suitable for exchanging between services.
+///
+/// `timestamp_bits` excludes any version bits, `shard_bits` excludes any
+/// expiry class bits and `sequence_bits` excludes any checksum bits. With the `serde` feature the
+/// fields after `unit` are optional and default to an unsplit, unversioned
+/// `[timestamp | shard | sequence]` layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutSpec {
+    /// Milliseconds since the Unix epoch.
+    pub epoch: u64,
+    pub timestamp_bits: u8,
+    pub shard_bits: u8,
+    pub sequence_bits: u8,
+    pub unit: TimeUnit,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub field_order: FieldOrder,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rounding: Rounding,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub datacenter_bits: u8,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub version_bits: u8,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub version: u8,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub expiry_class_bits: u8,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checksum_bits: u8,
+}
+
+impl LayoutSpec {
+    /// Validate the description and build the layout it describes.
+    ///
+    /// Any total width up to 64 bits is accepted, since specs describe IDs
+    /// minted elsewhere.
+    pub fn to_layout(&self) -> Result<Layout, BuildError> {
+        let total = self.version_bits as u32
+            + self.timestamp_bits as u32
+            + self.shard_bits as u32
+            + self.expiry_class_bits as u32
+            + self.sequence_bits as u32
+            + self.checksum_bits as u32;
+        if total > 64 {
+            return Err(BuildError::InvalidTotalBits { total });
+        }
+
+        let layout = Layout::with_widths(
+            self.timestamp_bits.saturating_add(self.version_bits),
+            self.shard_bits.saturating_add(self.expiry_class_bits),
+            self.sequence_bits.saturating_add(self.checksum_bits),
+        )?
+        .with_epoch(self.epoch)
+        .with_unit(self.unit)
+        .with_field_order(self.field_order)
+        .with_rounding(self.rounding)
+        .split_shard(self.datacenter_bits)?
+        .with_expiry_class(self.expiry_class_bits)?
+        .with_checksum(self.checksum_bits)?;
+
+        if self.version_bits == 0 {
+            Ok(layout)
+        } else {
+            layout.with_version(self.version_bits, self.version)
+        }
+    }
+}
+
+impl From<Layout> for LayoutSpec {
+    fn from(layout: Layout) -> Self {
+        layout.spec()
+    }
+}
+
+impl TryFrom<LayoutSpec> for Layout {
+    type Error = BuildError;
+
+    fn try_from(spec: LayoutSpec) -> Result<Self, Self::Error> {
+        spec.to_layout()
+    }
+}
+
+const fn mask(bits: u8) -> u64 {
+    if bits >= 64 {
+        u64::MAX
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+// Mask of a field at `shift`; empty fields may sit at shift 64
+const fn field_mask(bits: u8, shift: u32) -> u64 {
+    if bits == 0 {
+        0
+    } else {
+        mask(bits) << shift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_validation() {
+        assert!(Layout::new(41, 13, 10).is_ok());
+        assert!(Layout::new(42, 12, 10).is_ok());
+        assert_eq!(
+            Layout::new(39, 13, 10),
+            Err(BuildError::InvalidTotalBits { total: 62 })
+        );
+        assert!(matches!(
+            Layout::new_js_safe(41, 13, 10),
+            Err(BuildError::NotJsSafe { total: 64 })
+        ));
+        assert!(Layout::new_js_safe(41, 5, 7).unwrap().is_js_safe());
+        assert!(matches!(
+            Layout::new_i64_safe(41, 13, 10),
+            Err(BuildError::NotI64Safe { total: 64 })
+        ));
+        assert!(Layout::new_i64_safe(41, 12, 10).unwrap().is_i64_safe());
+        assert!(!Layout::DEFAULT.is_i64_safe());
+        assert!(matches!(
+            Layout::new(30, 17, 17),
+            Err(BuildError::FieldTooWide { field: "shard", .. })
+        ));
+    }
+
+    #[test]
+    fn test_layout_masks() {
+        let layout = Layout::DEFAULT
+            .with_version(2, 1)
+            .unwrap()
+            .with_expiry_class(3)
+            .unwrap()
+            .with_checksum(4)
+            .unwrap();
+        let masks = [
+            layout.version_mask(),
+            layout.timestamp_mask(),
+            layout.expiry_class_mask(),
+            layout.shard_mask(),
+            layout.sequence_mask(),
+            layout.checksum_mask(),
+        ];
+        assert_eq!(masks.iter().fold(0, |all, mask| all | mask), u64::MAX);
+        assert_eq!(masks.iter().map(|mask| mask.count_ones()).sum::<u32>(), 64);
+        assert_eq!(
+            layout.shard_mask() >> layout.shard_shift(),
+            layout.max_shard_id()
+        );
+
+        assert_eq!(Layout::DEFAULT.version_mask(), 0);
+        assert_eq!(Layout::DEFAULT.timestamp_mask(), 0x1FF_FFFF_FFFF << 23);
+    }
+
+    #[test]
+    fn test_layout_round_trip() {
+        let layout = Layout::new(39, 8, 16).unwrap();
+        let id = layout.encode(123_456, 200, 65_000);
+
+        assert_eq!(layout.extract_raw_timestamp(id), 123_456);
+        assert_eq!(layout.extract_timestamp(id), crate::CUSTOM_EPOCH + 123_456);
+        assert_eq!(layout.extract_shard_id(id), 200);
+        assert_eq!(layout.extract_sequence(id), 65_000);
+    }
+
+    #[test]
+    fn test_layout_custom_epoch() {
+        let epoch_2020 = 1_577_836_800_000;
+        let layout = Layout::DEFAULT.with_epoch(epoch_2020);
+        let id = layout.encode(1_000, 1, 0);
+
+        assert_eq!(layout.epoch(), epoch_2020);
+        assert_eq!(layout.extract_timestamp(id), epoch_2020 + 1_000);
+        assert_eq!(
+            Layout::DEFAULT.extract_timestamp(id),
+            crate::CUSTOM_EPOCH + 1_000
+        );
+    }
+
+    #[test]
+    fn test_layout_time_units() {
+        let seconds = Layout::DEFAULT.with_unit(TimeUnit::Seconds);
+        let now = Duration::from_millis(crate::CUSTOM_EPOCH + 90_500);
+        assert_eq!(seconds.ticks_at(now), 90);
+        assert_eq!(seconds.until_next_tick(now), Duration::from_millis(500));
+        let id = seconds.encode(seconds.ticks_at(now), 0, 0);
+        assert_eq!(seconds.extract_timestamp(id), crate::CUSTOM_EPOCH + 90_000);
+
+        let micros = Layout::DEFAULT.with_unit(TimeUnit::Micros);
+        let now = Duration::from_micros(crate::CUSTOM_EPOCH * 1_000 + 1_234_567);
+        assert_eq!(micros.ticks_at(now), 1_234_567);
+        let id = micros.encode(micros.ticks_at(now), 0, 0);
+        assert_eq!(micros.extract_timestamp(id), crate::CUSTOM_EPOCH + 1_234);
+    }
+
+    #[test]
+    fn test_layout_rounding() {
+        let nearest = Layout::DEFAULT
+            .with_unit(TimeUnit::Seconds)
+            .with_rounding(Rounding::Nearest);
+        let now = Duration::from_millis(crate::CUSTOM_EPOCH + 90_500);
+        assert_eq!(nearest.ticks_at(now), 91);
+        assert_eq!(nearest.until_next_tick(now), Duration::from_secs(1));
+        let now = Duration::from_millis(crate::CUSTOM_EPOCH + 90_499);
+        assert_eq!(nearest.ticks_at(now), 90);
+        assert_eq!(nearest.until_next_tick(now), Duration::from_millis(1));
+
+        let early = Duration::from_millis(crate::CUSTOM_EPOCH - 400);
+        assert_eq!(nearest.checked_ticks_at(early), Some(0));
+        assert_eq!(Layout::DEFAULT.checked_ticks_at(early), None);
+        assert_eq!(nearest.spec().to_layout(), Ok(nearest));
+    }
+
+    #[test]
+    fn test_layout_sequence_first() {
+        let layout = Layout::new(39, 16, 8)
+            .unwrap()
+            .with_field_order(FieldOrder::SequenceFirst);
+        let id = layout.encode(1, 0xBEEF, 0x42);
+
+        assert_eq!(id, (1 << 24) | (0x42 << 16) | 0xBEEF);
+        assert_eq!(layout.extract_shard_id(id), 0xBEEF);
+        assert_eq!(layout.extract_sequence(id), 0x42);
+    }
+
+    #[test]
+    fn test_layout_datacenter_split() {
+        let layout = Layout::DEFAULT.split_shard(4).unwrap();
+        assert_eq!(layout.max_datacenter_id(), 15);
+        assert_eq!(layout.max_worker_id(), 511);
+
+        let shard_id = layout.shard_id_from(9, 300);
+        let id = layout.encode(1, shard_id as u64, 0);
+        assert_eq!(layout.extract_datacenter(id), 9);
+        assert_eq!(layout.extract_worker(id), 300);
+        assert_eq!(layout.extract_shard_id(id), (9 << 9) | 300);
+
+        assert_eq!(Layout::DEFAULT.extract_datacenter(id), 0);
+        assert!(Layout::DEFAULT.split_shard(14).is_err());
+    }
+
+    #[test]
+    fn test_layout_version_bits() {
+        let layout = Layout::DEFAULT.with_version(3, 5).unwrap();
+        assert_eq!(layout.timestamp_bits(), 38);
+        assert_eq!(layout.total_bits(), 64);
+
+        let id = layout.encode(layout.max_timestamp(), 1, 2);
+        assert_eq!(id >> 61, 5);
+        assert_eq!(layout.extract_version(id), 5);
+        assert_eq!(layout.extract_raw_timestamp(id), layout.max_timestamp());
+
+        assert!(matches!(
+            Layout::DEFAULT.with_version(2, 4),
+            Err(BuildError::VersionOutOfRange { version: 4, max: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_layout_version_replaced() {
+        let twice = Layout::DEFAULT
+            .with_version(3, 5)
+            .unwrap()
+            .with_version(2, 1)
+            .unwrap();
+        assert_eq!(twice, Layout::DEFAULT.with_version(2, 1).unwrap());
+        assert_eq!(twice.timestamp_bits(), 39);
+
+        // The bits reserved by the first call count towards the second
+        let narrow = Layout::new_short(4, 14, 14)
+            .unwrap()
+            .with_version(3, 0)
+            .unwrap();
+        assert_eq!(narrow.with_version(3, 1).unwrap().timestamp_bits(), 1);
+        assert!(matches!(
+            narrow.with_version(4, 0),
+            Err(BuildError::FieldTooWide {
+                field: "version",
+                bits: 4,
+                max: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_layout_spec_round_trip() {
+        let layout = Layout::SONYFLAKE;
+        assert_eq!(layout.spec().to_layout(), Ok(layout));
+
+        let versioned = Layout::DEFAULT
+            .split_shard(3)
+            .unwrap()
+            .with_version(2, 1)
+            .unwrap();
+        assert_eq!(Layout::try_from(versioned.spec()), Ok(versioned));
+
+        let mut spec = Layout::DEFAULT.spec();
+        spec.sequence_bits = 20;
+        assert!(spec.to_layout().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_layout_spec_serde() {
+        let json = r#"{"epoch":1288834974657,"timestamp_bits":41,"shard_bits":10,"sequence_bits":12,"unit":"millis"}"#;
+        let spec: LayoutSpec = serde_json::from_str(json).unwrap();
+        let layout = spec.to_layout().unwrap();
+
+        assert_eq!(layout.extract_timestamp(1 << 22), 1288834974658);
+        let encoded = serde_json::to_string(&Layout::SONYFLAKE.spec()).unwrap();
+        assert!(encoded.contains(r#""unit":"ten_millis""#));
+    }
+
+    #[test]
+    fn test_layout_checksum() {
+        let layout = Layout::DEFAULT.with_checksum(4).unwrap();
+        assert_eq!(layout.sequence_bits(), 6);
+        assert_eq!(layout.total_bits(), 64);
+
+        let id = layout.encode(123_456_789, 42, 17);
+        assert!(layout.verify_checksum(id));
+        assert_eq!(layout.extract_shard_id(id), 42);
+        assert_eq!(layout.extract_sequence(id), 17);
+        assert_eq!(layout.spec().to_layout(), Ok(layout));
+
+        // Every single-bit corruption above the checksum is caught
+        for bit in 4..64 {
+            assert!(!layout.verify_checksum(id ^ (1 << bit)), "bit {}", bit);
+        }
+        assert!(Layout::DEFAULT.verify_checksum(id));
+    }
+
+    #[test]
+    fn test_layout_expiry_class() {
+        let layout = Layout::DEFAULT
+            .with_expiry_class(2)
+            .unwrap()
+            .with_checksum(2)
+            .unwrap();
+        assert_eq!(layout.shard_bits(), 11);
+        assert_eq!(layout.total_bits(), 64);
+
+        let id = layout.encode(99, 2047, 200);
+        assert_eq!(layout.extract_expiry_class(id), 0);
+
+        let classified = layout.set_expiry_class(id, 3);
+        assert_eq!(layout.extract_expiry_class(classified), 3);
+        assert_eq!(layout.extract_raw_timestamp(classified), 99);
+        assert_eq!(layout.extract_shard_id(classified), 2047);
+        assert_eq!(layout.extract_sequence(classified), 200);
+        assert!(layout.verify_checksum(classified));
+        assert_eq!(layout.spec().to_layout(), Ok(layout));
+    }
+}
@@ -1,40 +1,189 @@
-use std::sync::Mutex;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-const CUSTOM_EPOCH: u64 = 1704067200000; // 2024-01-01 00:00:00 UTC
-const SHARD_ID_BITS: u8 = 13;
-const SEQUENCE_BITS: u8 = 10;
+mod age;
+mod alphabet;
+mod avro;
+mod base58;
+mod base62;
+mod base64;
+#[cfg(feature = "borsh")]
+mod borsh_ext;
+#[cfg(feature = "bson")]
+mod bson_ext;
+mod builder;
+mod capacity;
+#[cfg(feature = "chrono")]
+mod chrono_ext;
+mod clock;
+mod const_generator;
+mod crockford;
+mod decode;
+mod duplicate;
+mod error;
+mod explain;
+mod format;
+mod hex;
+mod hilo;
+mod id;
+mod id128;
+mod ksuid;
+mod layout;
+mod macros;
+mod msgpack;
+mod obfuscate;
+mod object_id;
+mod ordering;
+mod persist;
+#[cfg(feature = "postgres-types")]
+mod postgres_ext;
+pub mod presets;
+mod random;
+mod range;
+#[cfg(feature = "redis")]
+mod redis_ext;
+#[cfg(feature = "rkyv")]
+mod rkyv_ext;
+#[cfg(feature = "rusqlite")]
+mod rusqlite_ext;
+mod salted;
+mod sequence;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod shared;
+mod short;
+mod skew;
+#[cfg(feature = "sqlx")]
+mod sqlx_ext;
+pub mod testing;
+#[cfg(feature = "time")]
+mod time_ext;
+mod ulid;
+#[cfg(feature = "uuid")]
+mod uuid_ext;
+mod validate;
+mod varint;
 
-const MAX_SHARD_ID: u64 = (1 << SHARD_ID_BITS) - 1;
-const MAX_SEQUENCE: u64 = (1 << SEQUENCE_BITS) - 1;
+pub use alphabet::{Alphabet, AlphabetError};
+pub use avro::{decode_avro_long, encode_avro_long, AVRO_LOGICAL_TYPE};
+pub use base58::{decode_base58, encode_base58, encode_base58_into};
+pub use base62::{decode_base62, encode_base62, encode_base62_into};
+pub use base64::{decode_base64, encode_base64, encode_base64_into};
+#[cfg(feature = "bson")]
+pub use bson_ext::FromBsonError;
+pub use builder::{BuildError, IdGeneratorBuilder};
+pub use capacity::RiskReport;
+use clock::ClockHook;
+#[cfg(all(feature = "coarse-clock", target_os = "linux"))]
+pub use clock::CoarseClock;
+#[cfg(feature = "quanta")]
+pub use clock::QuantaClock;
+pub use clock::{
+    CachedClock, ClockEvent, ForwardStepPolicy, MonotonicClock, SystemClock, TimeSource,
+};
+pub use const_generator::ConstIdGenerator;
+pub use crockford::{
+    decode_crockford, decode_crockford_with_check, encode_crockford, encode_crockford_into,
+    encode_crockford_with_check,
+};
+pub use decode::{decode_foreign, decode_with, DecodeError, IdComponents, VersionedLayouts};
+pub use duplicate::DuplicateDetector;
+pub use error::BanuidError;
+pub use explain::IdInfo;
+pub use format::{format_with, parse_with, FormatOptions};
+pub use hex::{decode_hex, encode_hex, encode_hex_into};
+#[cfg(feature = "fd-lock")]
+pub use hilo::FileHiAllocator;
+pub use hilo::{HiAllocator, HiLoGenerator, InMemoryHiAllocator};
+pub use id::{Id, ParseIdError};
+pub use id128::{Id128, IdGenerator128};
+pub use ksuid::Ksuid;
+pub use layout::{FieldOrder, Layout, LayoutSpec, Rounding, TimeUnit};
+pub use msgpack::{decode_msgpack_ext, encode_msgpack_ext, MsgpackExtError, MSGPACK_EXT_LEN};
+pub use obfuscate::{deobfuscate, obfuscate, IdMask};
+pub use object_id::ObjectId;
+pub use ordering::TimeOrdering;
+pub use persist::{FileStateStore, StateStore};
+pub use range::IdBuckets;
+pub use salted::SaltedEncoder;
+pub use sequence::SequenceMode;
+pub use shared::SharedGenerator;
+pub use short::ShortIdGenerator;
+pub use skew::SkewEstimator;
+pub use ulid::{ParseUlidError, Ulid};
+pub use validate::InvalidId;
+pub use varint::{decode_deltas, decode_varint, encode_deltas, encode_varint, VarintError};
 
-const SHARD_ID_SHIFT: u8 = SEQUENCE_BITS;
-const TIMESTAMP_SHIFT: u8 = SHARD_ID_BITS + SEQUENCE_BITS;
+/// Epoch of the default layout in milliseconds since the Unix epoch, 2024-01-01 00:00:00 UTC
+pub const CUSTOM_EPOCH: u64 = 1704067200000;
+/// Timestamp width of the default layout
+pub const TIMESTAMP_BITS: u8 = 41;
+/// Shard ID width of the default layout
+pub const SHARD_ID_BITS: u8 = 13;
+/// Sequence width of the default layout
+pub const SEQUENCE_BITS: u8 = 10;
+
+/// Largest shard ID of the default layout
+pub const MAX_SHARD_ID: u64 = (1 << SHARD_ID_BITS) - 1;
+
+// How long try_next_id sleeps on an exhausted tick without the clock moving
+const STALL_LIMIT: Duration = Duration::from_secs(1);
 
 struct GeneratorState {
     last_timestamp: u64,
     sequence: u64,
+    // Time used and monotonic reading at the last ID, for forward jump detection
+    last_read: Option<(Duration, Instant)>,
+    // Whether IDs are being held at the last timestamp through a leap second
+    leap_hold: bool,
+    // Whether the wall clock is ahead of the monotonic clock by a forward step
+    stepping: bool,
+    // Last tick recorded in the state store; later ticks need a new lease
+    reserved_until: u64,
 }
 
 pub struct IdGenerator {
-    shard_id: u16,
+    // Only changed while holding the state lock, see rotate_shard
+    shard_id: AtomicU16,
+    layout: Layout,
+    sequence_mode: SequenceMode,
+    max_spillover: u64,
+    rollback_tolerance: Duration,
+    clock: Arc<dyn TimeSource>,
+    state_store: Option<Arc<dyn StateStore>>,
+    // Ticks reserved ahead of the current one on each state store write
+    state_lease: u64,
+    clock_hook: Option<ClockHook>,
+    forward_jump_threshold: Duration,
+    forward_step_policy: ForwardStepPolicy,
+    leap_second_hold: bool,
     state: Mutex<GeneratorState>,
 }
 
-// Module-level generator for convenience API
-static DEFAULT_GENERATOR: std::sync::LazyLock<IdGenerator> =
-    std::sync::LazyLock::new(IdGenerator::new);
+// Support for macro expansions in downstream crates, not public API
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::id::parse_prefixed;
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
+
+// Module-level generator for convenience API, set by init or on first use
+static DEFAULT_GENERATOR: OnceLock<IdGenerator> = OnceLock::new();
+
+fn default_generator() -> &'static IdGenerator {
+    DEFAULT_GENERATOR.get_or_init(IdGenerator::new)
+}
 
 impl IdGenerator {
     pub fn new() -> Self {
-        let shard_id = derive_shard_id();
-        IdGenerator {
-            shard_id,
-            state: Mutex::new(GeneratorState {
-                last_timestamp: 0,
-                sequence: 0,
-            }),
-        }
+        Self::from_parts(derive_shard_id(), Layout::DEFAULT)
+    }
+
+    /// Start configuring a generator with a custom layout
+    pub fn builder() -> IdGeneratorBuilder {
+        IdGeneratorBuilder::new()
     }
 
     /// Generate an ID using this instance (new ergonomic method)
@@ -42,97 +191,526 @@ impl IdGenerator {
         self.next_id()
     }
 
+    /// Generator with a fixed shard ID, masked to the low 13 bits.
+    ///
+    /// Use [`try_with_shard_id`](Self::try_with_shard_id) to reject IDs that
+    /// would otherwise wrap onto another shard.
     pub fn with_shard_id(shard_id: u16) -> Self {
         let shard_id = shard_id & (MAX_SHARD_ID as u16);
+        Self::from_parts(shard_id, Layout::DEFAULT)
+    }
+
+    /// Generator with a fixed shard ID, failing if it does not fit in 13 bits.
+    pub fn try_with_shard_id(shard_id: u16) -> Result<Self, BuildError> {
+        if shard_id as u64 > MAX_SHARD_ID {
+            return Err(BuildError::ShardIdOutOfRange {
+                shard_id,
+                max: MAX_SHARD_ID as u16,
+            });
+        }
+        Ok(Self::from_parts(shard_id, Layout::DEFAULT))
+    }
+
+    pub(crate) fn from_parts(shard_id: u16, layout: Layout) -> Self {
         IdGenerator {
-            shard_id,
+            shard_id: AtomicU16::new(shard_id),
+            layout,
+            sequence_mode: SequenceMode::Counter,
+            max_spillover: 0,
+            rollback_tolerance: Duration::ZERO,
+            clock: Arc::new(SystemClock),
+            state_store: None,
+            state_lease: 0,
+            clock_hook: None,
+            forward_jump_threshold: Duration::from_secs(1),
+            forward_step_policy: ForwardStepPolicy::Accept,
+            leap_second_hold: false,
             state: Mutex::new(GeneratorState {
                 last_timestamp: 0,
                 sequence: 0,
+                last_read: None,
+                leap_hold: false,
+                stepping: false,
+                reserved_until: 0,
             }),
         }
     }
 
+    /// Generate the next ID.
+    ///
+    /// If the clock moves backwards, waits until it catches up with the last
+    /// issued timestamp; use [`try_next_id`](Self::try_next_id) to be told instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clock reads outside the range of timestamps the layout can
+    /// encode, if the [state store](IdGeneratorBuilder::state_store) cannot
+    /// be written, or if a random [`SequenceMode`] cannot read OS entropy.
     pub fn next_id(&self) -> u64 {
         loop {
-            let mut state = self.state.lock().unwrap();
-            let timestamp = current_timestamp();
+            match self.try_next_id() {
+                Ok(id) => return id,
+                Err(BanuidError::ClockMovedBackwards { by_ms }) => {
+                    self.clock.sleep(Duration::from_millis(by_ms));
+                }
+                Err(BanuidError::ClockJumpedForward { .. } | BanuidError::SequenceExhausted) => {}
+                Err(err) => panic!("{}", err),
+            }
+        }
+    }
+
+    /// Generate the next ID, failing instead of issuing an out-of-order or
+    /// duplicate ID when the clock has moved backwards by more than the
+    /// [rollback tolerance](IdGeneratorBuilder::rollback_tolerance).
+    ///
+    /// Waits for the next tick when the current one is exhausted, but returns
+    /// [`BanuidError::SequenceExhausted`] if the clock stops advancing for
+    /// more than a second.
+    pub fn try_next_id(&self) -> Result<u64, BanuidError> {
+        self.generate_id(true)
+    }
+
+    /// Generate the next ID without ever sleeping.
+    ///
+    /// Returns [`BanuidError::SequenceExhausted`] as soon as the current tick
+    /// is used up, and [`BanuidError::ClockMovedBackwards`] for any clock
+    /// regression it cannot absorb, so latency-sensitive callers can shed load
+    /// or retry elsewhere.
+    pub fn next_id_nonblocking(&self) -> Result<u64, BanuidError> {
+        self.generate_id(false)
+    }
+
+    fn generate_id(&self, block: bool) -> Result<u64, BanuidError> {
+        // Last clock reading and how long we have slept without it moving
+        let mut stall = (Duration::ZERO, Duration::ZERO);
+        loop {
+            let entropy = match self.sequence_mode {
+                SequenceMode::Random | SequenceMode::RandomLowBits(_) => random::secure_u64()?,
+                _ => 0,
+            };
+            let mut state = self.lock_state();
+            let (now, jump) = self.forward_step(&mut state, self.clock.now());
+            if let Some(ClockEvent::JumpedForward { by_ms }) = jump {
+                if self.forward_step_policy == ForwardStepPolicy::Error {
+                    drop(state);
+                    self.emit(jump);
+                    return Err(BanuidError::ClockJumpedForward { by_ms });
+                }
+            }
+            let Some(current) = self.layout.checked_ticks_at(now) else {
+                drop(state);
+                self.emit(jump);
+                let epoch = Duration::from_millis(self.layout.epoch());
+                return Err(BanuidError::ClockBeforeEpoch {
+                    by_ms: (epoch - now).as_micros().div_ceil(1_000) as u64,
+                });
+            };
+            let mut timestamp = current;
+            let previous = (state.last_timestamp, state.sequence);
 
-            if timestamp == state.last_timestamp {
-                if state.sequence >= MAX_SEQUENCE {
+            let mut leap = None;
+            let mut allowance = self.max_spillover;
+            if current < state.last_timestamp {
+                let behind = state.last_timestamp - current;
+                let second = 1_000_000 / self.layout.unit().as_micros();
+                if self.leap_second_hold && behind > self.max_spillover && behind <= second {
+                    allowance = allowance.max(second);
+                    if !state.leap_hold {
+                        state.leap_hold = true;
+                        leap = Some(ClockEvent::LeapSecond {
+                            by_ms: behind * self.layout.unit().as_micros() / 1_000,
+                        });
+                    }
+                }
+                if behind > allowance {
+                    let by = Duration::from_micros(behind * self.layout.unit().as_micros());
+                    let by_ms = by.as_micros().div_ceil(1_000) as u64;
                     drop(state);
-                    std::thread::sleep(std::time::Duration::from_millis(1));
-                    continue;
+                    self.emit(jump);
+                    self.emit(leap);
+                    self.emit(Some(ClockEvent::MovedBackwards { by_ms }));
+                    if block && by <= self.rollback_tolerance {
+                        self.clock.sleep(by);
+                        continue;
+                    }
+                    return Err(BanuidError::ClockMovedBackwards { by_ms });
                 }
-                state.sequence += 1;
-                let sequence = state.sequence;
-                return ((timestamp - CUSTOM_EPOCH) << TIMESTAMP_SHIFT)
-                    | ((self.shard_id as u64) << SHARD_ID_SHIFT)
-                    | sequence;
+                // Keep issuing from a borrowed tick until the clock catches up
+                timestamp = state.last_timestamp;
             } else {
-                state.last_timestamp = timestamp;
-                state.sequence = 0;
-                return ((timestamp - CUSTOM_EPOCH) << TIMESTAMP_SHIFT)
-                    | ((self.shard_id as u64) << SHARD_ID_SHIFT)
-                    | 0;
+                state.leap_hold = false;
+            }
+
+            let sequence = match self.sequence_mode {
+                SequenceMode::Counter
+                | SequenceMode::RandomStart
+                | SequenceMode::RandomLowBits(_) => {
+                    if timestamp == state.last_timestamp {
+                        if state.sequence < self.max_counter() {
+                            state.sequence += 1;
+                        } else if state.last_timestamp - current < allowance {
+                            state.last_timestamp += 1;
+                            state.sequence = self.sequence_start();
+                            timestamp = state.last_timestamp;
+                        } else {
+                            drop(state);
+                            self.emit(jump);
+                            self.emit(leap);
+                            self.emit(Some(ClockEvent::SequenceExhausted));
+                            if now > stall.0 {
+                                stall = (now, Duration::ZERO);
+                            }
+                            if !block || stall.1 >= STALL_LIMIT {
+                                return Err(BanuidError::SequenceExhausted);
+                            }
+                            let wait = self.layout.until_next_tick(now);
+                            stall.1 += wait;
+                            self.clock.sleep(wait);
+                            continue;
+                        }
+                    } else {
+                        state.last_timestamp = timestamp;
+                        state.sequence = self.sequence_start();
+                    }
+                    self.pad_sequence(state.sequence, entropy)
+                }
+                SequenceMode::Random => {
+                    state.last_timestamp = timestamp;
+                    entropy
+                }
+            };
+
+            let result = self.issue(&mut state, timestamp, sequence, previous);
+            drop(state);
+            self.emit(jump);
+            self.emit(leap);
+            return result;
+        }
+    }
+
+    // Encode an ID once its timestamp and sequence are settled, reserving a
+    // new lease first if the timestamp passed the stored one
+    fn issue(
+        &self,
+        state: &mut GeneratorState,
+        timestamp: u64,
+        sequence: u64,
+        previous: (u64, u64),
+    ) -> Result<u64, BanuidError> {
+        if timestamp > self.layout.max_timestamp() {
+            return Err(BanuidError::TimestampOverflow);
+        }
+        if let Some(store) = &self.state_store {
+            if state.last_timestamp > state.reserved_until {
+                let until = (state.last_timestamp.saturating_add(self.state_lease))
+                    .min(self.layout.max_timestamp());
+                if let Err(err) = store.reserve(until) {
+                    // Stay within the old lease so the next call retries
+                    (state.last_timestamp, state.sequence) = previous;
+                    return Err(BanuidError::StatePersistence(err.kind()));
+                }
+                state.reserved_until = until;
+            }
+        }
+
+        let shard_id = self.shard_id.load(Ordering::Relaxed);
+        Ok(self.layout.encode(timestamp, shard_id as u64, sequence))
+    }
+
+    // Compare wall clock progress against the monotonic clock since the last
+    // ID, returning the time to use under the forward step policy
+    fn forward_step(
+        &self,
+        state: &mut GeneratorState,
+        wall: Duration,
+    ) -> (Duration, Option<ClockEvent>) {
+        if self.clock_hook.is_none() && self.forward_step_policy == ForwardStepPolicy::Accept {
+            return (wall, None);
+        }
+        let instant = Instant::now();
+        let Some((last, monotonic)) = state.last_read else {
+            state.last_read = Some((wall, instant));
+            return (wall, None);
+        };
+
+        let elapsed = instant.duration_since(monotonic);
+        let skew = wall.saturating_sub(last).saturating_sub(elapsed);
+        let stepping = skew > self.forward_jump_threshold;
+        let now = if stepping && self.forward_step_policy == ForwardStepPolicy::Clamp {
+            last + elapsed * 2
+        } else {
+            wall
+        };
+        state.last_read = Some((now, instant));
+
+        let event = (stepping && !state.stepping).then_some(ClockEvent::JumpedForward {
+            by_ms: skew.as_millis() as u64,
+        });
+        state.stepping = stepping && self.forward_step_policy == ForwardStepPolicy::Clamp;
+        (now, event)
+    }
+
+    fn emit(&self, event: Option<ClockEvent>) {
+        if let (Some(hook), Some(event)) = (&self.clock_hook, event) {
+            (hook.0)(event);
+        }
+    }
+
+    /// Merge a timestamp from a remote ID so every ID generated afterwards sorts after it.
+    ///
+    /// Meant for [hybrid logical clock](IdGeneratorBuilder::hybrid_logical_clock)
+    /// generators sharing a layout; elsewhere a remote ID ahead of the local
+    /// clock is treated like a clock regression until the clock catches up.
+    pub fn observe(&self, remote_id: u64) {
+        let remote = self.layout.extract_raw_timestamp(remote_id);
+        let mut state = self.lock_state();
+        if remote >= state.last_timestamp {
+            state.last_timestamp = remote;
+            state.sequence = self.layout.max_sequence();
+        }
+    }
+
+    /// Switch to `shard_id`, e.g. when a coordinator reassigns this process's
+    /// shard lease.
+    ///
+    /// IDs generated afterwards carry the new shard and start from the next
+    /// tick, so they cannot collide with IDs the shard's previous holder issued
+    /// in the current one.
+    pub fn rotate_shard(&self, shard_id: u16) -> Result<(), BuildError> {
+        if shard_id as u64 > self.layout.max_shard_id() {
+            return Err(BuildError::ShardIdOutOfRange {
+                shard_id,
+                max: self.layout.max_shard_id() as u16,
+            });
+        }
+        let mut state = self.lock_state();
+        let current = self.layout.checked_ticks_at(self.clock.now()).unwrap_or(0);
+        state.last_timestamp = state.last_timestamp.max(current);
+        state.sequence = self.layout.max_sequence();
+        self.shard_id.store(shard_id, Ordering::Relaxed);
+        Ok(())
+    }
+
+    // Treat every tick up to a restored lease as used, so the next ID comes
+    // from a later one
+    pub(crate) fn resume_from(&self, timestamp: u64) {
+        let mut state = self.lock_state();
+        state.last_timestamp = timestamp;
+        state.sequence = self.layout.max_sequence();
+        state.reserved_until = timestamp;
+    }
+
+    // The state only ever moves forward, so it stays valid even if a thread
+    // panicked while holding the lock
+    fn lock_state(&self) -> MutexGuard<'_, GeneratorState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // First sequence value of a new tick
+    fn sequence_start(&self) -> u64 {
+        match self.sequence_mode {
+            SequenceMode::RandomStart => {
+                random::random_u64() % (self.layout.max_sequence() / 2 + 1)
             }
+            _ => 0,
         }
     }
 
-    pub fn extract_timestamp(id: u64) -> u64 {
-        ((id >> TIMESTAMP_SHIFT) as u64) + CUSTOM_EPOCH
+    // Number of random low bits below the counter
+    fn random_bits(&self) -> u32 {
+        match self.sequence_mode {
+            SequenceMode::RandomLowBits(bits) => bits as u32,
+            _ => 0,
+        }
+    }
+
+    // Largest counter value that fits above the random low bits
+    fn max_counter(&self) -> u64 {
+        self.layout.max_sequence() >> self.random_bits()
+    }
+
+    // Sequence field for a counter value, with random low bits appended
+    fn pad_sequence(&self, counter: u64, entropy: u64) -> u64 {
+        let bits = self.random_bits();
+        if bits == 0 {
+            return counter;
+        }
+        (counter << bits) | (entropy & ((1 << bits) - 1))
+    }
+
+    /// Generate an ID tagged with an expiry class.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `class` does not fit the layout's expiry class field.
+    pub fn next_id_with_expiry_class(&self, class: u8) -> u64 {
+        assert!(
+            class <= self.layout.max_expiry_class(),
+            "expiry class {} exceeds the layout maximum of {}",
+            class,
+            self.layout.max_expiry_class()
+        );
+        self.layout.set_expiry_class(self.next_id(), class)
+    }
+
+    pub const fn extract_timestamp(id: u64) -> u64 {
+        Layout::DEFAULT.extract_timestamp(id)
+    }
+
+    /// Extract the timestamp of an ID minted with the default layout but a custom epoch
+    pub const fn extract_timestamp_with_epoch(id: u64, epoch: u64) -> u64 {
+        Layout::DEFAULT.with_epoch(epoch).extract_timestamp(id)
+    }
+
+    pub const fn extract_shard_id(id: u64) -> u16 {
+        Layout::DEFAULT.extract_shard_id(id)
     }
 
-    pub fn extract_shard_id(id: u64) -> u16 {
-        ((id >> SHARD_ID_SHIFT) & MAX_SHARD_ID) as u16
+    pub const fn extract_sequence(id: u64) -> u16 {
+        Layout::DEFAULT.extract_sequence(id)
     }
 
-    pub fn extract_sequence(id: u64) -> u16 {
-        (id & MAX_SEQUENCE) as u16
+    /// Decode every field of an ID minted with the default layout in one call.
+    pub const fn decode(id: u64) -> IdComponents {
+        Layout::DEFAULT.decode(id)
     }
 
     /// Parse timestamp from ID (new ergonomic method)
-    pub fn parse_timestamp(id: u64) -> u64 {
+    pub const fn parse_timestamp(id: u64) -> u64 {
         Self::extract_timestamp(id)
     }
 
     /// Parse shard ID from ID (new ergonomic method)
-    pub fn parse_shard_id(id: u64) -> u16 {
+    pub const fn parse_shard_id(id: u64) -> u16 {
         Self::extract_shard_id(id)
     }
 
     /// Parse sequence from ID (new ergonomic method)
-    pub fn parse_sequence(id: u64) -> u16 {
+    pub const fn parse_sequence(id: u64) -> u16 {
         Self::extract_sequence(id)
     }
 
     pub fn shard_id(&self) -> u16 {
-        self.shard_id
+        self.shard_id.load(Ordering::Relaxed)
+    }
+
+    /// Datacenter part of this generator's shard ID
+    pub fn datacenter_id(&self) -> u16 {
+        (self.shard_id() as u64 >> self.layout.worker_bits()) as u16
+    }
+
+    /// Worker part of this generator's shard ID
+    pub fn worker_id(&self) -> u16 {
+        (self.shard_id() as u64 & self.layout.max_worker_id()) as u16
+    }
+
+    pub fn sequence_mode(&self) -> SequenceMode {
+        self.sequence_mode
+    }
+
+    /// How many ticks ahead of the clock a burst may borrow, see
+    /// [`IdGeneratorBuilder::max_spillover`].
+    pub fn max_spillover(&self) -> u64 {
+        self.max_spillover
+    }
+
+    /// Largest clock regression [`try_next_id`](Self::try_next_id) waits out instead of failing
+    pub fn rollback_tolerance(&self) -> Duration {
+        self.rollback_tolerance
+    }
+
+    /// The bit layout used by this generator
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    /// The bit layout as plain data, e.g. for FFI bindings or other services
+    pub fn layout_spec(&self) -> LayoutSpec {
+        self.layout.spec()
+    }
+}
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 // Convenience free functions for ergonomic API
+/// Configure the generator behind [`generate`] and the other free functions.
+///
+/// Must be called before any of them is first used; afterwards the default
+/// generator is fixed and this fails with [`BuildError::AlreadyInitialized`].
+///
+/// ```
+/// let config = banuid::IdGenerator::builder()
+///     .shard_id(42)
+///     .epoch_millis(1_577_836_800_000);
+/// banuid::init(config).unwrap();
+///
+/// let id = banuid::generate();
+/// assert_eq!(banuid::parse_shard_id(id), 42);
+/// assert!(banuid::init(banuid::IdGenerator::builder()).is_err());
+/// ```
+pub fn init(config: IdGeneratorBuilder) -> Result<(), BuildError> {
+    DEFAULT_GENERATOR
+        .set(config.build()?)
+        .map_err(|_| BuildError::AlreadyInitialized)
+}
+
 /// Generate a unique ID using default generator
 pub fn generate() -> u64 {
-    DEFAULT_GENERATOR.next_id()
+    default_generator().next_id()
+}
+
+/// Decode timestamp, shard ID and sequence from ID using default generator methods
+pub fn decode(id: u64) -> IdComponents {
+    default_generator().layout().decode(id)
 }
 
 /// Parse timestamp from ID using default generator methods
 pub fn parse_timestamp(id: u64) -> u64 {
-    IdGenerator::extract_timestamp(id)
+    default_generator().layout().extract_timestamp(id)
 }
 
 /// Parse shard ID from ID using default generator methods
 pub fn parse_shard_id(id: u64) -> u16 {
-    IdGenerator::extract_shard_id(id)
+    default_generator().layout().extract_shard_id(id)
 }
 
 /// Parse sequence from ID using default generator methods
 pub fn parse_sequence(id: u64) -> u16 {
-    IdGenerator::extract_sequence(id)
+    default_generator().layout().extract_sequence(id)
+}
+
+/// Time elapsed since an ID minted with the default layout was created
+pub fn age_of(id: u64) -> Duration {
+    default_generator().age_of(id)
 }
 
-fn derive_shard_id() -> u16 {
+/// Whether an ID minted with the default layout was created no longer than `window` ago
+pub fn was_created_within(id: u64, window: Duration) -> bool {
+    default_generator().was_created_within(id, window)
+}
+
+/// Check that an ID could have been minted with the default layout, see [`Layout::validate`]
+pub fn validate(id: u64) -> Result<(), InvalidId> {
+    default_generator().validate(id)
+}
+
+/// Convert an ID to a signed integer, or `None` if its top bit is set.
+///
+/// IDs from an i64-safe layout (see [`IdGeneratorBuilder::i64_safe`]) always convert.
+pub fn as_i64(id: u64) -> Option<i64> {
+    i64::try_from(id).ok()
+}
+
+/// Convert a signed integer read from a BIGINT column back to an ID.
+pub fn from_i64(value: i64) -> Option<u64> {
+    u64::try_from(value).ok()
+}
+
+pub(crate) fn derive_shard_id() -> u16 {
     let mut hash: u64 = 14695981039346656037; // FNV offset basis
     const FNV_PRIME: u64 = 1099511628211;
     let mut has_identifier = false;
@@ -178,25 +756,31 @@ fn derive_shard_id() -> u16 {
 fn get_fallback_random() -> u32 {
     // Multi-layer fallback for random number generation
 
+    // Layer 0: Operating system entropy
+    #[cfg(feature = "getrandom")]
+    if let Ok(value) = getrandom::u32() {
+        return value;
+    }
+
     // Layer 1: System time nanoseconds
     if let Ok(duration) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
         return duration.subsec_nanos();
     }
 
-    // Layer 2: Memory address of a stack variable (non-deterministic)
+    // Layer 2: Memory address of a stack variable, only as a last resort
     let stack_var = 0u64;
     let stack_addr = &stack_var as *const u64 as usize;
-    return (stack_addr & 0xFFFFFFFF) as u32;
+    (stack_addr & 0xFFFFFFFF) as u32
 }
 
-fn current_timestamp() -> u64 {
+pub(crate) fn current_time() -> Duration {
     match SystemTime::now().duration_since(UNIX_EPOCH) {
-        Ok(duration) => duration.as_millis() as u64,
+        Ok(duration) => duration,
         Err(_) => {
             // Fallback: use combination of fallback random and process start time
             let base_time = get_fallback_random() as u64;
             let pid_component = std::process::id() as u64;
-            base_time ^ (pid_component << 16)
+            Duration::from_millis(base_time ^ (pid_component << 16))
         }
     }
 }
@@ -204,8 +788,10 @@ fn current_timestamp() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::MockClock;
 
     #[test]
+    #[allow(clippy::double_comparisons)]
     fn test_id_generation() {
         let generator = IdGenerator::with_shard_id(42);
         let id1 = generator.next_id();
@@ -220,7 +806,7 @@ mod tests {
         let generator = IdGenerator::with_shard_id(1);
         let id = generator.next_id();
         let extracted = IdGenerator::extract_timestamp(id);
-        let now = current_timestamp();
+        let now = current_time().as_millis() as u64;
 
         assert!(
             extracted <= now && extracted >= now - 1000,
@@ -238,6 +824,20 @@ mod tests {
         assert_eq!(extracted, shard_id, "Shard ID should match");
     }
 
+    #[test]
+    fn test_const_extraction() {
+        const ID: u64 = Layout::DEFAULT.encode(1_000, 42, 7);
+        const TIMESTAMP: u64 = IdGenerator::extract_timestamp(ID);
+        const SHARD_ID: u16 = IdGenerator::extract_shard_id(ID);
+        const SEQUENCE: u16 = IdGenerator::parse_sequence(ID);
+        const SHARD_MASK: u64 = Layout::DEFAULT.shard_mask();
+
+        assert_eq!(TIMESTAMP, CUSTOM_EPOCH + 1_000);
+        assert_eq!(SHARD_ID, 42);
+        assert_eq!(SEQUENCE, 7);
+        assert_eq!(SHARD_MASK, MAX_SHARD_ID << SEQUENCE_BITS);
+    }
+
     #[test]
     fn test_shard_id_bounds() {
         let generator = IdGenerator::with_shard_id(8191); // Max 13-bit value
@@ -245,6 +845,18 @@ mod tests {
 
         let generator2 = IdGenerator::with_shard_id(10000); // Overflow
         assert_eq!(generator2.shard_id(), 10000 & (MAX_SHARD_ID as u16));
+
+        assert_eq!(
+            IdGenerator::try_with_shard_id(8191).unwrap().shard_id(),
+            8191
+        );
+        assert!(matches!(
+            IdGenerator::try_with_shard_id(10000),
+            Err(BuildError::ShardIdOutOfRange {
+                shard_id: 10000,
+                max: 8191
+            })
+        ));
     }
 
     #[test]
@@ -306,6 +918,14 @@ mod tests {
         assert!(shard2 <= MAX_SHARD_ID as u16);
     }
 
+    #[test]
+    fn test_i64_conversion() {
+        assert_eq!(as_i64(42), Some(42));
+        assert_eq!(as_i64(u64::MAX), None);
+        assert_eq!(from_i64(i64::MAX), Some(i64::MAX as u64));
+        assert_eq!(from_i64(-1), None);
+    }
+
     #[test]
     fn test_ergonomic_api() {
         // Test free functions
@@ -337,4 +957,510 @@ mod tests {
         assert_eq!(shard_id2, IdGenerator::extract_shard_id(id3));
         assert_eq!(sequence2, IdGenerator::extract_sequence(id3));
     }
+
+    #[test]
+    fn test_sequence_spillover() {
+        // 16 IDs per second; a 2-tick spillover allows bursts of 48 without waiting
+        let generator = IdGenerator::builder()
+            .unit(TimeUnit::Seconds)
+            .timestamp_bits(44)
+            .shard_bits(16)
+            .sequence_bits(4)
+            .max_spillover(2)
+            .shard_id(1)
+            .build()
+            .unwrap();
+        assert_eq!(generator.max_spillover(), 2);
+
+        let start = std::time::Instant::now();
+        let ids: Vec<u64> = (0..48).map(|_| generator.next_id()).collect();
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let layout = generator.layout();
+        let now = layout.ticks_at(current_time());
+        let last = ids[47] >> 20;
+        assert!(last <= now + 2);
+        assert_eq!(layout.extract_sequence(ids[47]), 15);
+    }
+
+    #[test]
+    fn test_random_start_sequence() {
+        let generator = IdGenerator::builder()
+            .sequence_mode(SequenceMode::RandomStart)
+            .shard_id(3)
+            .build()
+            .unwrap();
+
+        let ids: Vec<u64> = (0..2000).map(|_| generator.next_id()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let layout = generator.layout();
+        let starts: Vec<u16> = ids
+            .windows(2)
+            .filter(|pair| {
+                layout.extract_raw_timestamp(pair[0]) != layout.extract_raw_timestamp(pair[1])
+            })
+            .map(|pair| layout.extract_sequence(pair[1]))
+            .collect();
+        assert!(starts.iter().all(|&start| start <= 511));
+        assert!(starts.iter().any(|&start| start != 0));
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn test_random_low_bits_sequence() {
+        let generator = IdGenerator::builder()
+            .sequence_mode(SequenceMode::RandomLowBits(8))
+            .shard_id(3)
+            .build()
+            .unwrap();
+
+        let ids: Vec<u64> = (0..200).map(|_| generator.next_id()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // 10 sequence bits leave a 2-bit counter above the random ones
+        let layout = generator.layout();
+        assert!(ids.iter().all(|&id| layout.extract_sequence(id) >> 8 <= 3));
+        assert!(ids
+            .iter()
+            .any(|&id| layout.extract_sequence(id) & 0xFF != 0));
+
+        let result = IdGenerator::builder()
+            .sequence_mode(SequenceMode::RandomLowBits(12))
+            .build();
+        assert_eq!(
+            result.err(),
+            Some(BuildError::FieldTooWide {
+                field: "random",
+                bits: 12,
+                max: 9
+            })
+        );
+    }
+
+    #[test]
+    fn test_clock_rollback_detected() {
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 1_000);
+        let generator = IdGenerator::builder()
+            .time_source(clock.clone())
+            .shard_id(2)
+            .build()
+            .unwrap();
+
+        let before = generator.next_id();
+        clock.set(crate::CUSTOM_EPOCH + 750);
+        assert_eq!(
+            generator.try_next_id(),
+            Err(crate::BanuidError::ClockMovedBackwards { by_ms: 250 })
+        );
+
+        // The infallible method waits the regression out
+        let after = generator.next_id();
+        assert!(after > before);
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 1_000);
+    }
+
+    #[test]
+    fn test_rollback_tolerance() {
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 1_000);
+        let generator = IdGenerator::builder()
+            .rollback_tolerance(Duration::from_millis(5))
+            .time_source(clock.clone())
+            .shard_id(2)
+            .build()
+            .unwrap();
+
+        let before = generator.try_next_id().unwrap();
+        clock.set(crate::CUSTOM_EPOCH + 996);
+        assert!(generator.try_next_id().unwrap() > before);
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 1_000);
+
+        clock.set(crate::CUSTOM_EPOCH + 900);
+        assert_eq!(
+            generator.try_next_id(),
+            Err(crate::BanuidError::ClockMovedBackwards { by_ms: 100 })
+        );
+    }
+
+    #[test]
+    fn test_hybrid_logical_clock() {
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 1_000);
+        let generator = IdGenerator::builder()
+            .sequence_bits(4)
+            .shard_bits(16)
+            .timestamp_bits(44)
+            .hybrid_logical_clock()
+            .time_source(clock.clone())
+            .shard_id(1)
+            .build()
+            .unwrap();
+        let layout = *generator.layout();
+
+        // A peer whose clock runs 50 ms ahead
+        let remote = layout.encode(1_050, 7, 3);
+        generator.observe(remote);
+        let id = generator.try_next_id().unwrap();
+        assert!(id > remote);
+        assert_eq!(layout.extract_raw_timestamp(id), 1_051);
+
+        // Regressions and exhausted ticks advance the logical clock instead
+        clock.set(crate::CUSTOM_EPOCH + 500);
+        let ids: Vec<u64> = (0..40).map(|_| generator.try_next_id().unwrap()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids[0] > id);
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 500);
+    }
+
+    #[test]
+    fn test_state_file_survives_restart() {
+        let path = std::env::temp_dir().join(format!("banuid-state-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 1_000);
+        let build = || {
+            IdGenerator::builder()
+                .time_source(clock.clone())
+                .state_file(&path)
+                .shard_id(4)
+                .build()
+                .unwrap()
+        };
+
+        let last = build().next_id();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "2000");
+
+        // Restarted within the lease, waits for the clock to pass it
+        let restarted = build();
+        assert!(restarted.next_id() > last);
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 2_001);
+
+        // Restarted after the clock stepped backwards
+        clock.set(crate::CUSTOM_EPOCH + 990);
+        assert!(matches!(
+            build().try_next_id(),
+            Err(crate::BanuidError::ClockMovedBackwards { .. })
+        ));
+
+        std::fs::write(&path, "garbage").unwrap();
+        assert!(IdGenerator::builder().state_file(&path).build().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_clock_event_hook() {
+        use crate::ClockEvent;
+        use std::sync::Mutex;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&events);
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 1_000);
+        let generator = IdGenerator::builder()
+            .timestamp_bits(44)
+            .shard_bits(16)
+            .sequence_bits(4)
+            .time_source(clock.clone())
+            .on_clock_event(move |event| seen.lock().unwrap().push(event))
+            .shard_id(1)
+            .build()
+            .unwrap();
+
+        for _ in 0..17 {
+            generator.next_id();
+        }
+        clock.advance(60_000);
+        generator.next_id();
+        clock.set(crate::CUSTOM_EPOCH + 50_000);
+        let _ = generator.try_next_id();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events[0], ClockEvent::SequenceExhausted);
+        assert!(matches!(events[1], ClockEvent::JumpedForward { by_ms } if by_ms >= 59_000));
+        assert_eq!(events[2], ClockEvent::MovedBackwards { by_ms: 11_001 });
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_clock_before_epoch() {
+        let clock = MockClock::new(crate::CUSTOM_EPOCH - 1_500);
+        let generator = IdGenerator::builder()
+            .time_source(clock.clone())
+            .shard_id(1)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            generator.try_next_id(),
+            Err(crate::BanuidError::ClockBeforeEpoch { by_ms: 1_500 })
+        );
+        clock.advance(1_500);
+        assert_eq!(
+            generator.layout().extract_timestamp(generator.next_id()),
+            crate::CUSTOM_EPOCH
+        );
+    }
+
+    #[test]
+    fn test_leap_second_hold() {
+        use crate::ClockEvent;
+        use std::sync::Mutex;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&events);
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 10_000);
+        let generator = IdGenerator::builder()
+            .timestamp_bits(44)
+            .shard_bits(16)
+            .sequence_bits(4)
+            .leap_second_hold()
+            .time_source(clock.clone())
+            .on_clock_event(move |event| seen.lock().unwrap().push(event))
+            .shard_id(1)
+            .build()
+            .unwrap();
+
+        let before = generator.try_next_id().unwrap();
+        clock.set(crate::CUSTOM_EPOCH + 9_000);
+        let held: Vec<u64> = (0..40).map(|_| generator.try_next_id().unwrap()).collect();
+        assert!(held[0] > before);
+        assert!(held.windows(2).all(|pair| pair[0] < pair[1]));
+        // Held IDs keep one tick per elapsed tick instead of waiting a full second
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 9_002);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                ClockEvent::LeapSecond { by_ms: 1_000 },
+                ClockEvent::SequenceExhausted,
+                ClockEvent::SequenceExhausted,
+            ]
+        );
+
+        clock.set(crate::CUSTOM_EPOCH + 5_000);
+        assert!(generator.try_next_id().is_err());
+    }
+
+    #[test]
+    fn test_forward_step_policies() {
+        use crate::ForwardStepPolicy;
+
+        let build = |clock: &MockClock, policy| {
+            IdGenerator::builder()
+                .forward_step_policy(policy)
+                .time_source(clock.clone())
+                .shard_id(1)
+                .build()
+                .unwrap()
+        };
+
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 1_000);
+        let generator = build(&clock, ForwardStepPolicy::Error);
+        generator.try_next_id().unwrap();
+        clock.advance(3_600_000);
+        assert!(matches!(
+            generator.try_next_id(),
+            Err(crate::BanuidError::ClockJumpedForward { by_ms }) if by_ms >= 3_599_000
+        ));
+        let id = generator.try_next_id().unwrap();
+        assert!(generator.layout().extract_timestamp(id) >= crate::CUSTOM_EPOCH + 3_601_000);
+
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 1_000);
+        let generator = build(&clock, ForwardStepPolicy::Clamp);
+        generator.try_next_id().unwrap();
+        clock.advance(3_600_000);
+        let id = generator.try_next_id().unwrap();
+        assert!(generator.layout().extract_timestamp(id) < crate::CUSTOM_EPOCH + 60_000);
+    }
+
+    #[test]
+    fn test_stalled_clock_exhausts_sequence() {
+        use crate::BanuidError;
+
+        // A clock that never moves, even while the generator sleeps
+        #[derive(Debug)]
+        struct FrozenClock;
+        impl TimeSource for FrozenClock {
+            fn now(&self) -> Duration {
+                Duration::from_millis(crate::CUSTOM_EPOCH + 10)
+            }
+            fn sleep(&self, _: Duration) {}
+        }
+
+        let generator = IdGenerator::builder()
+            .timestamp_bits(44)
+            .shard_bits(16)
+            .sequence_bits(4)
+            .time_source(FrozenClock)
+            .build()
+            .unwrap();
+        for _ in 0..16 {
+            generator.try_next_id().unwrap();
+        }
+        assert_eq!(generator.try_next_id(), Err(BanuidError::SequenceExhausted));
+    }
+
+    #[test]
+    fn test_recovers_from_poisoned_lock() {
+        use std::panic::{self, AssertUnwindSafe};
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        #[derive(Debug)]
+        struct PanickingClock(AtomicBool);
+        impl TimeSource for PanickingClock {
+            fn now(&self) -> Duration {
+                assert!(!self.0.swap(false, Ordering::SeqCst), "clock failure");
+                Duration::from_millis(crate::CUSTOM_EPOCH + 10)
+            }
+        }
+
+        let generator = IdGenerator::builder()
+            .time_source(PanickingClock(AtomicBool::new(true)))
+            .build()
+            .unwrap();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| generator.try_next_id()));
+        assert!(result.is_err());
+        assert!(generator.try_next_id().is_ok());
+        assert!(generator.next_id() > 0);
+    }
+
+    #[test]
+    fn test_next_id_nonblocking() {
+        use crate::BanuidError;
+
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 10);
+        let generator = IdGenerator::builder()
+            .timestamp_bits(44)
+            .shard_bits(16)
+            .sequence_bits(4)
+            .rollback_tolerance(Duration::from_millis(50))
+            .time_source(clock.clone())
+            .build()
+            .unwrap();
+        for _ in 0..16 {
+            generator.next_id_nonblocking().unwrap();
+        }
+        assert_eq!(
+            generator.next_id_nonblocking(),
+            Err(BanuidError::SequenceExhausted)
+        );
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 10);
+
+        clock.advance(1);
+        assert!(generator.next_id_nonblocking().is_ok());
+        clock.set(crate::CUSTOM_EPOCH + 1);
+        assert_eq!(
+            generator.next_id_nonblocking(),
+            Err(BanuidError::ClockMovedBackwards { by_ms: 10 })
+        );
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 1);
+    }
+
+    #[test]
+    fn test_strict_monotonic() {
+        use crate::{BuildError, SequenceMode};
+
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 10_000);
+        let generator = IdGenerator::builder()
+            .timestamp_bits(44)
+            .shard_bits(16)
+            .sequence_bits(4)
+            .strict_monotonic()
+            .max_spillover(0)
+            .time_source(clock.clone())
+            .build()
+            .unwrap();
+
+        let mut last = generator.try_next_id().unwrap();
+        for step in 0..100 {
+            if step % 10 == 0 {
+                clock.set(crate::CUSTOM_EPOCH + 10_000 - step * 50);
+            }
+            let id = generator.try_next_id().unwrap();
+            assert!(id > last);
+            last = id;
+        }
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 10_000 - 4_500);
+
+        let result = IdGenerator::builder()
+            .strict_monotonic()
+            .sequence_mode(SequenceMode::Random)
+            .build();
+        assert!(matches!(
+            result,
+            Err(BuildError::RandomSequenceNotMonotonic)
+        ));
+    }
+
+    #[test]
+    fn test_timestamp_overflow() {
+        use crate::BanuidError;
+
+        // 32 timestamp bits of milliseconds last about 49 days
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + (1 << 32) - 1);
+        let generator = IdGenerator::builder()
+            .timestamp_bits(32)
+            .shard_bits(16)
+            .sequence_bits(16)
+            .time_source(clock.clone())
+            .build()
+            .unwrap();
+
+        let id = generator.try_next_id().unwrap();
+        assert_eq!(generator.layout().extract_raw_timestamp(id), (1 << 32) - 1);
+        clock.advance(1);
+        assert_eq!(generator.try_next_id(), Err(BanuidError::TimestampOverflow));
+    }
+
+    #[test]
+    fn test_clock_events_reported_on_errors() {
+        use crate::{BanuidError, ClockEvent};
+        use std::sync::Mutex;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&events);
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + (1 << 32) - 30_000);
+        let generator = IdGenerator::builder()
+            .timestamp_bits(32)
+            .shard_bits(16)
+            .sequence_bits(16)
+            .time_source(clock.clone())
+            .on_clock_event(move |event| seen.lock().unwrap().push(event))
+            .build()
+            .unwrap();
+
+        generator.next_id();
+        // A forward step past the end of the timestamp range
+        clock.advance(60_000);
+        assert_eq!(generator.try_next_id(), Err(BanuidError::TimestampOverflow));
+
+        let events = events.lock().unwrap();
+        assert!(matches!(events[..], [ClockEvent::JumpedForward { by_ms }] if by_ms >= 59_000));
+    }
+
+    #[test]
+    fn test_rotate_shard() {
+        use crate::BuildError;
+
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 1_000);
+        let generator = IdGenerator::builder()
+            .shard_id(1)
+            .time_source(clock.clone())
+            .build()
+            .unwrap();
+        let layout = *generator.layout();
+
+        let before = generator.next_id();
+        generator.rotate_shard(2).unwrap();
+        assert_eq!(generator.shard_id(), 2);
+
+        let after = generator.next_id();
+        assert_eq!(layout.extract_shard_id(after), 2);
+        assert_eq!(layout.extract_sequence(after), 0);
+        assert_eq!(
+            layout.extract_raw_timestamp(after),
+            layout.extract_raw_timestamp(before) + 1
+        );
+        assert!(matches!(
+            generator.rotate_shard(8192),
+            Err(BuildError::ShardIdOutOfRange { .. })
+        ));
+    }
 }
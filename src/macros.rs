@@ -0,0 +1,195 @@
+/// Define a newtype ID for one kind of entity.
+///
+/// The type wraps a `NonZeroU64` and implements `Display`, `FromStr`,
+/// ordering, hashing and conversions to and from `u64`, plus serde support
+/// when the `serde` feature is enabled. Like [`Id`](crate::Id), it rejects
+/// zero wherever it is built from an integer or string. With a prefix, IDs display and serialize as
+/// `{prefix}_{id}` strings, otherwise as plain integers.
+///
+/// ```
+/// banuid::define_id!(
+///     /// Identifies an order.
+///     pub OrderId, prefix = "ord"
+/// );
+/// banuid::define_id!(pub UserId);
+///
+/// let order = OrderId::generate();
+/// let text = order.to_string();
+/// assert!(text.starts_with("ord_"));
+/// assert_eq!(text.parse::<OrderId>(), Ok(order));
+/// assert!(text.parse::<UserId>().is_err());
+/// assert_eq!(UserId::from_u64(7).unwrap().to_string(), "7");
+/// assert_eq!(UserId::from_u64(0), None);
+/// ```
+#[macro_export]
+macro_rules! define_id {
+    ($(#[$meta:meta])* $vis:vis $name:ident) => {
+        $crate::define_id!($(#[$meta])* $vis $name, prefix = "");
+    };
+    ($(#[$meta:meta])* $vis:vis $name:ident, prefix = $prefix:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name(::std::num::NonZeroU64);
+
+        impl $name {
+            /// Prefix written before the numeric ID, or empty for none.
+            pub const PREFIX: &'static str = $prefix;
+
+            /// Generate a new ID from the default generator.
+            pub fn generate() -> Self {
+                loop {
+                    if let ::std::option::Option::Some(id) = Self::from_u64($crate::generate()) {
+                        return id;
+                    }
+                }
+            }
+
+            /// `None` if `value` is zero.
+            pub const fn from_u64(value: u64) -> ::std::option::Option<Self> {
+                match ::std::num::NonZeroU64::new(value) {
+                    ::std::option::Option::Some(value) => ::std::option::Option::Some($name(value)),
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            pub const fn as_u64(&self) -> u64 {
+                self.0.get()
+            }
+        }
+
+        impl ::std::convert::TryFrom<u64> for $name {
+            type Error = $crate::ParseIdError;
+
+            fn try_from(value: u64) -> ::std::result::Result<Self, Self::Error> {
+                Self::from_u64(value).ok_or($crate::ParseIdError::Zero)
+            }
+        }
+
+        impl ::std::convert::From<$name> for u64 {
+            fn from(id: $name) -> Self {
+                id.0.get()
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                if Self::PREFIX.is_empty() {
+                    ::std::write!(f, "{}", self.0)
+                } else {
+                    ::std::write!(f, "{}_{}", Self::PREFIX, self.0)
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::ParseIdError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                let value = $crate::__private::parse_prefixed(s, Self::PREFIX)?;
+                Self::from_u64(value).ok_or($crate::ParseIdError::Zero)
+            }
+        }
+
+        $crate::__define_id_serde!($name);
+    };
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                if Self::PREFIX.is_empty() {
+                    serializer.serialize_u64(self.as_u64())
+                } else {
+                    serializer.collect_str(self)
+                }
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                use $crate::__private::serde::de::Error;
+                if Self::PREFIX.is_empty() {
+                    let value = <u64 as $crate::__private::serde::Deserialize>::deserialize(deserializer)?;
+                    Self::from_u64(value).ok_or_else(|| D::Error::custom($crate::ParseIdError::Zero))
+                } else {
+                    let s = <::std::string::String as $crate::__private::serde::Deserialize>::deserialize(
+                        deserializer,
+                    )?;
+                    s.parse().map_err(D::Error::custom)
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_serde {
+    ($name:ident) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ParseIdError;
+
+    crate::define_id!(OrderId, prefix = "ord");
+    crate::define_id!(
+        /// Doc comments pass through.
+        pub(crate) RawId
+    );
+
+    #[test]
+    fn test_define_id() {
+        let order = OrderId::from_u64(42).unwrap();
+        assert_eq!(order.to_string(), "ord_42");
+        assert_eq!("ord_42".parse(), Ok(order));
+        assert_eq!(
+            "usr_42".parse::<OrderId>(),
+            Err(ParseIdError::MissingPrefix { expected: "ord" })
+        );
+        assert!(matches!(
+            "ord_x".parse::<OrderId>(),
+            Err(ParseIdError::InvalidNumber(_))
+        ));
+
+        assert_eq!(RawId::try_from(42).unwrap().to_string(), "42");
+        assert_eq!("42".parse(), RawId::try_from(42));
+        assert!(RawId::generate() < RawId::generate());
+        assert!(OrderId::generate().as_u64() > RawId::try_from(42).unwrap().as_u64());
+
+        // Zero is rejected like it is for Id
+        assert_eq!(RawId::from_u64(0), None);
+        assert_eq!(RawId::try_from(0), Err(ParseIdError::Zero));
+        assert_eq!("ord_0".parse::<OrderId>(), Err(ParseIdError::Zero));
+        assert_eq!(std::mem::size_of::<Option<RawId>>(), 8);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_define_id_serde() {
+        let order = OrderId::from_u64(42).unwrap();
+        assert_eq!(serde_json::to_string(&order).unwrap(), "\"ord_42\"");
+        assert_eq!(
+            serde_json::from_str::<OrderId>("\"ord_42\"").unwrap(),
+            order
+        );
+        assert!(serde_json::from_str::<OrderId>("42").is_err());
+
+        let raw = RawId::from_u64(42).unwrap();
+        assert_eq!(serde_json::to_string(&raw).unwrap(), "42");
+        assert_eq!(serde_json::from_str::<RawId>("42").unwrap(), raw);
+        assert!(serde_json::from_str::<RawId>("0").is_err());
+        assert!(serde_json::from_str::<OrderId>("\"ord_0\"").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+
+/// MessagePack format byte for an extension value with 8 data bytes.
+const FIXEXT8: u8 = 0xD7;
+
+/// Bytes in an encoded ID: format, type and 8 data bytes.
+pub const MSGPACK_EXT_LEN: usize = 10;
+
+/// Encode `id` as a MessagePack `fixext 8` value of application type
+/// `ext_type` (0 to 127), so msgpack peers can tell IDs apart from ordinary
+/// integers.
+///
+/// The data bytes are the ID in big-endian order, so libraries that expose
+/// raw extension values (e.g. `rmpv::Value::Ext`) can decode it with
+/// `u64::from_be_bytes`.
+///
+/// # Panics
+///
+/// Panics if `ext_type` is negative; those types are reserved by MessagePack.
+///
+/// ```
+/// let bytes = banuid::encode_msgpack_ext(0x0102, 7);
+/// assert_eq!(bytes, [0xD7, 7, 0, 0, 0, 0, 0, 0, 1, 2]);
+/// assert_eq!(banuid::decode_msgpack_ext(&bytes, 7), Ok((0x0102, 10)));
+/// ```
+pub fn encode_msgpack_ext(id: u64, ext_type: i8) -> [u8; MSGPACK_EXT_LEN] {
+    assert!(
+        ext_type >= 0,
+        "msgpack extension types below 0 are reserved"
+    );
+    let mut bytes = [0; MSGPACK_EXT_LEN];
+    bytes[0] = FIXEXT8;
+    bytes[1] = ext_type as u8;
+    bytes[2..].copy_from_slice(&id.to_be_bytes());
+    bytes
+}
+
+/// Read an ID written by [`encode_msgpack_ext`] from the start of `bytes`,
+/// returning it with the number of bytes consumed.
+///
+/// # Panics
+///
+/// Panics if `ext_type` is negative, as for [`encode_msgpack_ext`].
+pub fn decode_msgpack_ext(bytes: &[u8], ext_type: i8) -> Result<(u64, usize), MsgpackExtError> {
+    assert!(
+        ext_type >= 0,
+        "msgpack extension types below 0 are reserved"
+    );
+    let Some(header) = bytes.get(..2) else {
+        return Err(MsgpackExtError::Truncated);
+    };
+    if header[0] != FIXEXT8 {
+        return Err(MsgpackExtError::NotFixExt8(header[0]));
+    }
+    if header[1] as i8 != ext_type {
+        return Err(MsgpackExtError::WrongType {
+            expected: ext_type,
+            found: header[1] as i8,
+        });
+    }
+    let data = bytes
+        .get(2..MSGPACK_EXT_LEN)
+        .ok_or(MsgpackExtError::Truncated)?;
+    Ok((
+        u64::from_be_bytes(data.try_into().unwrap()),
+        MSGPACK_EXT_LEN,
+    ))
+}
+
+/// Error decoding an ID from MessagePack bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MsgpackExtError {
+    /// The input ended before the end of the value.
+    Truncated,
+    /// The value is not a `fixext 8`; holds the format byte found.
+    NotFixExt8(u8),
+    /// The extension type is not the one used for IDs.
+    WrongType { expected: i8, found: i8 },
+}
+
+impl fmt::Display for MsgpackExtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MsgpackExtError::Truncated => write!(f, "msgpack ID is truncated"),
+            MsgpackExtError::NotFixExt8(format) => {
+                write!(
+                    f,
+                    "expected msgpack fixext 8, found format byte {:#04x}",
+                    format
+                )
+            }
+            MsgpackExtError::WrongType { expected, found } => {
+                write!(
+                    f,
+                    "expected msgpack extension type {}, found {}",
+                    expected, found
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MsgpackExtError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_msgpack_ext() {
+        for id in [0, 1, 1 << 40, u64::MAX] {
+            let mut bytes = encode_msgpack_ext(id, 127).to_vec();
+            bytes.push(0xC0);
+            assert_eq!(decode_msgpack_ext(&bytes, 127), Ok((id, MSGPACK_EXT_LEN)));
+        }
+
+        let bytes = encode_msgpack_ext(42, 5);
+        assert_eq!(
+            decode_msgpack_ext(&bytes, 6),
+            Err(MsgpackExtError::WrongType {
+                expected: 6,
+                found: 5
+            })
+        );
+        assert_eq!(
+            decode_msgpack_ext(&bytes[..9], 5),
+            Err(MsgpackExtError::Truncated)
+        );
+        assert_eq!(decode_msgpack_ext(&[], 5), Err(MsgpackExtError::Truncated));
+        // A timestamp, which uses the reserved type -1
+        assert_eq!(
+            decode_msgpack_ext(&[0xD7, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0], 5),
+            Err(MsgpackExtError::WrongType {
+                expected: 5,
+                found: -1
+            })
+        );
+        // A plain msgpack uint64
+        assert_eq!(
+            decode_msgpack_ext(&[0xCF, 0, 0, 0, 0, 0, 0, 0, 42], 5),
+            Err(MsgpackExtError::NotFixExt8(0xCF))
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::base62::{decode_base62, encode_base62};
+use crate::id::ParseIdError;
+
+const ROUNDS: u64 = 8;
+
+/// Permute `id` under `key` so public IDs reveal nothing about creation time,
+/// shard or volume, while staying a `u64`. [`deobfuscate`] reverses it.
+///
+/// This is an 8-round Feistel network over the two 32-bit halves; every key
+/// gives a different one-to-one mapping of all `u64` values. It hides
+/// structure from casual observers but is not an audited cipher, so don't
+/// rely on it where IDs must stay secret against a determined attacker.
+///
+/// ```
+/// let key = 0x5EC2_E7C0_FFEE_1234;
+/// let id = banuid::generate();
+/// let public = banuid::obfuscate(id, key);
+/// assert_ne!(public, id);
+/// assert_eq!(banuid::deobfuscate(public, key), id);
+/// ```
+pub fn obfuscate(id: u64, key: u64) -> u64 {
+    let (mut left, mut right) = ((id >> 32) as u32, id as u32);
+    for round in 0..ROUNDS {
+        (left, right) = (right, left ^ round_function(right, key, round));
+    }
+    ((left as u64) << 32) | right as u64
+}
+
+/// Recover the ID passed to [`obfuscate`] with the same key.
+pub fn deobfuscate(id: u64, key: u64) -> u64 {
+    let (mut left, mut right) = ((id >> 32) as u32, id as u32);
+    for round in (0..ROUNDS).rev() {
+        (left, right) = (right ^ round_function(left, key, round), left);
+    }
+    ((left as u64) << 32) | right as u64
+}
+
+fn round_function(half: u32, key: u64, round: u64) -> u32 {
+    let round_key = mix64(key.wrapping_add(round.wrapping_mul(0x9E37_79B9_7F4A_7C15)));
+    (mix64(half as u64 ^ round_key) >> 32) as u32
+}
+
+/// SplitMix64 finalizer: a fast bijective mix where each input bit affects
+/// every output bit.
+pub(crate) const fn mix64(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Keyed XOR masking: a cheap deterrent against enumerating IDs on low-risk
+/// endpoints. [`encode`](Self::encode) masks IDs as they are handed out and
+/// [`decode`](Self::decode) removes the mask on the way in.
+///
+/// Each 32-bit half of the ID is XORed with a stream derived from the secret
+/// and the other half, so the mask differs per ID and one known ID does not
+/// reveal it for others. Two rounds are much weaker than [`obfuscate`]'s
+/// eight; use that where IDs must resist analysis.
+///
+/// ```
+/// use banuid::IdMask;
+///
+/// let mask = IdMask::new(b"per-deployment secret");
+/// let id = banuid::generate();
+/// assert_ne!(mask.mask(id), id);
+/// assert_eq!(mask.unmask(mask.mask(id)), id);
+///
+/// let public = mask.encode(id);
+/// assert_eq!(mask.decode(&public), Ok(id));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct IdMask {
+    key: u64,
+}
+
+impl IdMask {
+    pub fn new(secret: &[u8]) -> Self {
+        IdMask {
+            key: mix64(fnv1a(secret)),
+        }
+    }
+
+    pub const fn mask(&self, id: u64) -> u64 {
+        let (high, low) = ((id >> 32) as u32, id as u32);
+        let low = low ^ self.stream(high, 0);
+        let high = high ^ self.stream(low, 1);
+        ((high as u64) << 32) | low as u64
+    }
+
+    pub const fn unmask(&self, masked: u64) -> u64 {
+        let (high, low) = ((masked >> 32) as u32, masked as u32);
+        let high = high ^ self.stream(low, 1);
+        let low = low ^ self.stream(high, 0);
+        ((high as u64) << 32) | low as u64
+    }
+
+    /// Mask `id` and encode it as base62, see [`encode_base62`].
+    pub fn encode(&self, id: u64) -> String {
+        encode_base62(self.mask(id))
+    }
+
+    /// Decode a string from [`encode`](Self::encode) and remove the mask.
+    pub fn decode(&self, s: &str) -> Result<u64, ParseIdError> {
+        decode_base62(s).map(|masked| self.unmask(masked))
+    }
+
+    // Keystream for one half, derived from the secret and the other half
+    const fn stream(&self, half: u32, round: u64) -> u32 {
+        let input = mix64(((round << 32) | half as u64) ^ 0x9E37_79B9_7F4A_7C15);
+        (mix64(self.key ^ input) >> 32) as u32
+    }
+}
+
+// Keeps the key out of logs
+impl std::fmt::Debug for IdMask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("IdMask(..)")
+    }
+}
+
+// Stable across Rust versions and platforms, unlike std's hashers
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obfuscate_round_trip() {
+        let key = 42;
+        for id in [0, 1, 2, 1 << 40, u64::MAX] {
+            assert_eq!(deobfuscate(obfuscate(id, key), key), id);
+            assert_ne!(obfuscate(id, key), obfuscate(id, key + 1));
+        }
+
+        // Consecutive IDs land far apart
+        let a = obfuscate(1_000, key);
+        let b = obfuscate(1_001, key);
+        assert!((a ^ b).count_ones() > 16);
+        assert_ne!(deobfuscate(a, key + 1), 1_000);
+    }
+
+    #[test]
+    fn test_id_mask() {
+        let mask = IdMask::new(b"secret");
+        assert_eq!(mask, IdMask::new(b"secret"));
+        assert_ne!(mask, IdMask::new(b"secret2"));
+        for id in [0, 1, 1 << 40, u64::MAX] {
+            assert_eq!(mask.unmask(mask.mask(id)), id);
+        }
+        // The mask touches the high bits, hiding the timestamp
+        assert_ne!(mask.mask(0) >> 32, 0);
+        assert_eq!(format!("{:?}", mask), "IdMask(..)");
+
+        // The stream depends on the ID, so XOR differences are not preserved
+        let (a, b) = (1 << 40, (1 << 40) + 1);
+        assert_ne!(mask.mask(a) ^ mask.mask(b), a ^ b);
+        assert_ne!(mask.mask(a) ^ a, mask.mask(b) ^ b);
+
+        let encoded = mask.encode(a);
+        assert_ne!(encoded, encode_base62(a));
+        assert_eq!(mask.decode(&encoded), Ok(a));
+        assert_ne!(IdMask::new(b"other").decode(&encoded), Ok(a));
+        assert!(mask.decode("!").is_err());
+    }
+}
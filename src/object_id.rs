@@ -0,0 +1,159 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::decode::IdComponents;
+use crate::id::ParseIdError;
+use crate::layout::Layout;
+
+/// A MongoDB ObjectId: a big-endian 32-bit Unix timestamp in seconds, a
+/// 5-byte per-process random value and a 3-byte big-endian counter.
+///
+/// Displays and parses as 24 lowercase hex characters, like Mongo's tools.
+///
+/// ```
+/// use banuid::{Layout, ObjectId};
+///
+/// let oid: ObjectId = "65920080a1b2c3d4e5000007".parse().unwrap();
+/// let components = Layout::DEFAULT.object_id_components(&oid);
+/// assert_eq!(components.timestamp_ms, 1_704_067_200_000);
+/// assert_eq!(components.sequence, 7);
+/// assert_eq!(oid.to_string(), "65920080a1b2c3d4e5000007");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObjectId([u8; 12]);
+
+impl ObjectId {
+    pub const fn from_bytes(bytes: [u8; 12]) -> Self {
+        ObjectId(bytes)
+    }
+
+    pub const fn to_bytes(&self) -> [u8; 12] {
+        self.0
+    }
+
+    /// Seconds since the Unix epoch
+    pub const fn timestamp_secs(&self) -> u64 {
+        u32::from_be_bytes([self.0[0], self.0[1], self.0[2], self.0[3]]) as u64
+    }
+
+    /// The 5-byte value identifying the process that created the ObjectId
+    pub const fn machine(&self) -> u64 {
+        let b = self.0;
+        u64::from_be_bytes([0, 0, 0, b[4], b[5], b[6], b[7], b[8]])
+    }
+
+    pub const fn counter(&self) -> u32 {
+        u32::from_be_bytes([0, self.0[9], self.0[10], self.0[11]])
+    }
+}
+
+impl Layout {
+    /// Map an ObjectId's fields onto this layout's components.
+    ///
+    /// The timestamp is the start of the ObjectId's second; the shard and
+    /// sequence are the low bits of its machine value and counter.
+    pub fn object_id_components(&self, oid: &ObjectId) -> IdComponents {
+        IdComponents {
+            timestamp_ms: oid.timestamp_secs() * 1000,
+            shard_id: (oid.machine() & self.max_shard_id()) as u16,
+            sequence: (oid.counter() as u64 & self.max_sequence()) as u16,
+        }
+    }
+
+    /// Map an ObjectId to an ID in this layout, see
+    /// [`object_id_components`](Self::object_id_components). Distinct
+    /// ObjectIds can map to the same ID. Returns `None` if the ObjectId's
+    /// time lies outside the layout's range.
+    pub fn id_from_object_id(&self, oid: &ObjectId) -> Option<u64> {
+        let ticks = self.checked_ticks_at(Duration::from_secs(oid.timestamp_secs()))?;
+        if ticks > self.max_timestamp() {
+            return None;
+        }
+        Some(self.encode(ticks, oid.machine(), oid.counter() as u64))
+    }
+
+    /// Build an ObjectId from an ID minted with this layout, for storing
+    /// alongside Mongo documents. The timestamp keeps whole seconds, the
+    /// machine value holds the shard and the counter the sequence, so the ID
+    /// cannot be recovered exactly.
+    pub fn to_object_id(&self, id: u64) -> ObjectId {
+        let components = self.decode(id);
+        let secs = (components.timestamp_ms / 1000).min(u32::MAX as u64) as u32;
+        let mut bytes = [0; 12];
+        bytes[..4].copy_from_slice(&secs.to_be_bytes());
+        bytes[4..9].copy_from_slice(&(components.shard_id as u64).to_be_bytes()[3..]);
+        bytes[9..].copy_from_slice(&(components.sequence as u32).to_be_bytes()[1..]);
+        ObjectId(bytes)
+    }
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ObjectId {
+    type Err = ParseIdError;
+
+    /// Parse 24 hex characters of either case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 24 {
+            return Err(ParseIdError::InvalidLength(s.len()));
+        }
+        let mut bytes = [0; 12];
+        let mut digits = s
+            .chars()
+            .map(|c| c.to_digit(16).ok_or(ParseIdError::InvalidChar(c)));
+        for byte in bytes.iter_mut() {
+            // The length check guarantees two digits per byte
+            let high = digits.next().unwrap()?;
+            let low = digits.next().unwrap()?;
+            *byte = (high << 4 | low) as u8;
+        }
+        Ok(ObjectId(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_id_mapping() {
+        let oid: ObjectId = "65920081A1B2C3D4E5ABCDEF".parse().unwrap();
+        assert_eq!(oid.timestamp_secs(), 1_704_067_201);
+        assert_eq!(oid.machine(), 0xA1_B2C3_D4E5);
+        assert_eq!(oid.counter(), 0xABCDEF);
+        assert_eq!(oid.to_string(), "65920081a1b2c3d4e5abcdef");
+
+        let layout = Layout::DEFAULT;
+        let id = layout.id_from_object_id(&oid).unwrap();
+        assert_eq!(layout.decode(id), layout.object_id_components(&oid));
+        assert_eq!(layout.decode(id).timestamp_ms, 1_704_067_201_000);
+
+        // Only the bits that fit survive the round trip
+        let back = layout.to_object_id(id);
+        assert_eq!(back.timestamp_secs(), oid.timestamp_secs());
+        assert_eq!(back.machine(), oid.machine() & layout.max_shard_id());
+        assert_eq!(
+            back.counter() as u64,
+            oid.counter() as u64 & layout.max_sequence()
+        );
+
+        let before_epoch = ObjectId::from_bytes([0; 12]);
+        assert_eq!(layout.id_from_object_id(&before_epoch), None);
+        assert_eq!(
+            "65920081".parse::<ObjectId>(),
+            Err(ParseIdError::InvalidLength(8))
+        );
+        assert_eq!(
+            "65920081a1b2c3d4e5abcdeg".parse::<ObjectId>(),
+            Err(ParseIdError::InvalidChar('g'))
+        );
+    }
+}
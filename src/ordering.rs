@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+/// Result of comparing two IDs by creation time, see [`Layout::compare_by_time`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeOrdering {
+    /// The order is trustworthy.
+    Ordered(Ordering),
+    /// The IDs come from different shards and were created too close
+    /// together for clock skew to be ruled out.
+    Ambiguous,
+}
+
+impl Layout {
+    /// Order two IDs by creation time, treating IDs from different shards
+    /// created within `tolerance` of each other as [`TimeOrdering::Ambiguous`].
+    ///
+    /// IDs from the same shard come from one clock and always compare exactly.
+    pub fn compare_by_time(&self, a: u64, b: u64, tolerance: Duration) -> TimeOrdering {
+        if self.extract_shard_id(a) == self.extract_shard_id(b) {
+            return TimeOrdering::Ordered(self.sort_key(a).cmp(&self.sort_key(b)));
+        }
+        let (a_ms, b_ms) = (self.extract_timestamp(a), self.extract_timestamp(b));
+        if a_ms.abs_diff(b_ms) as u128 <= tolerance.as_millis() {
+            TimeOrdering::Ambiguous
+        } else {
+            TimeOrdering::Ordered(a_ms.cmp(&b_ms))
+        }
+    }
+
+    // Orders IDs from one shard by generation order, whatever the field order
+    fn sort_key(&self, id: u64) -> (u64, u16) {
+        (self.extract_raw_timestamp(id), self.extract_sequence(id))
+    }
+}
+
+impl IdGenerator {
+    /// Compare default-layout IDs by creation time, see [`Layout::compare_by_time`].
+    pub fn compare_by_time(a: u64, b: u64, tolerance: Duration) -> TimeOrdering {
+        Layout::DEFAULT.compare_by_time(a, b, tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_by_time() {
+        let layout = Layout::DEFAULT;
+        let tolerance = Duration::from_millis(10);
+
+        let early = layout.encode(1_000, 1, 5);
+        let same_shard_later = layout.encode(1_000, 1, 6);
+        let close_peer = layout.encode(1_008, 2, 0);
+        let far_peer = layout.encode(1_011, 2, 0);
+
+        assert_eq!(
+            layout.compare_by_time(early, same_shard_later, tolerance),
+            TimeOrdering::Ordered(Ordering::Less)
+        );
+        assert_eq!(
+            layout.compare_by_time(early, close_peer, tolerance),
+            TimeOrdering::Ambiguous
+        );
+        assert_eq!(
+            IdGenerator::compare_by_time(far_peer, early, tolerance),
+            TimeOrdering::Ordered(Ordering::Greater)
+        );
+        assert_eq!(
+            layout.compare_by_time(early, early, Duration::ZERO),
+            TimeOrdering::Ordered(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn test_compare_by_time_sequence_first() {
+        let layout = Layout::SONYFLAKE;
+        let a = layout.encode(100, 9, 3);
+        let b = layout.encode(100, 9, 4);
+        assert_eq!(
+            layout.compare_by_time(b, a, Duration::from_secs(1)),
+            TimeOrdering::Ordered(Ordering::Greater)
+        );
+    }
+}
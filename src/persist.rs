@@ -0,0 +1,170 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Where a generator records how far ahead it may issue IDs, so a restarted
+/// process never reissues IDs for a tick that was already used.
+///
+/// The generator [reserves](Self::reserve) a lease on upcoming ticks and only
+/// writes again once the clock passes it; on restart it resumes after the
+/// [loaded](Self::load) lease.
+///
+/// Implement it over shared storage (Redis, a database row, ...) when local
+/// disks are ephemeral. Install one with
+/// [`IdGeneratorBuilder::state_store`](crate::IdGeneratorBuilder::state_store).
+pub trait StateStore: fmt::Debug + Send + Sync {
+    /// End of the last reserved lease in layout ticks, or `None` if nothing
+    /// was reserved yet.
+    fn load(&self) -> io::Result<Option<u64>>;
+
+    /// Durably record that IDs may be issued for ticks up to and including
+    /// `until`, called once the previous lease is used up. IDs beyond the
+    /// previous lease are only issued after this returns `Ok`.
+    fn reserve(&self, until: u64) -> io::Result<()>;
+}
+
+/// Stores the generator's timestamp lease in a local file.
+///
+/// Reservations write a sibling `.tmp` file and rename it over the target, so a
+/// crash mid-write leaves the previous lease in place.
+#[derive(Debug, Clone)]
+pub struct FileStateStore {
+    path: PathBuf,
+}
+
+impl FileStateStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileStateStore { path: path.into() }
+    }
+}
+
+impl StateStore for FileStateStore {
+    fn load(&self) -> io::Result<Option<u64>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        contents
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    fn reserve(&self, until: u64) -> io::Result<()> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(until.to_string().as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockClock;
+    use crate::IdGenerator;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, Default)]
+    struct SharedStore(Arc<Mutex<Option<u64>>>);
+
+    impl StateStore for SharedStore {
+        fn load(&self) -> io::Result<Option<u64>> {
+            Ok(*self.0.lock().unwrap())
+        }
+
+        fn reserve(&self, until: u64) -> io::Result<()> {
+            *self.0.lock().unwrap() = Some(until);
+            Ok(())
+        }
+    }
+
+    // Fails the first reservation, then behaves like SharedStore
+    #[derive(Debug, Clone, Default)]
+    struct FlakyStore(SharedStore, Arc<Mutex<bool>>);
+
+    impl StateStore for FlakyStore {
+        fn load(&self) -> io::Result<Option<u64>> {
+            self.0.load()
+        }
+
+        fn reserve(&self, until: u64) -> io::Result<()> {
+            let mut failed = self.1.lock().unwrap();
+            if !*failed {
+                *failed = true;
+                return Err(io::Error::other("disk full"));
+            }
+            self.0.reserve(until)
+        }
+    }
+
+    #[test]
+    fn test_failed_reservation_is_retried() {
+        let store = FlakyStore::default();
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 2_000);
+        let generator = IdGenerator::builder()
+            .time_source(clock.clone())
+            .state_store(store.clone())
+            .shard_id(1)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            generator.try_next_id(),
+            Err(crate::BanuidError::StatePersistence(io::ErrorKind::Other))
+        );
+        assert_eq!(store.load().unwrap(), None);
+        // The tick is only used once a lease covering it has been saved
+        let id = generator.try_next_id().unwrap();
+        assert_eq!(store.load().unwrap(), Some(3_000));
+        assert_eq!(crate::Layout::DEFAULT.extract_sequence(id), 0);
+    }
+
+    #[test]
+    fn test_file_store_replaces_contents() {
+        let path = std::env::temp_dir().join(format!("banuid-state-file-{}", std::process::id()));
+        let store = FileStateStore::new(&path);
+        assert_eq!(store.load().unwrap(), None);
+
+        store.reserve(123_456).unwrap();
+        store.reserve(7).unwrap();
+        assert_eq!(store.load().unwrap(), Some(7));
+        assert!(!path.with_extension("tmp").exists());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_custom_state_store() {
+        let store = SharedStore::default();
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 2_000);
+        let build = || {
+            IdGenerator::builder()
+                .time_source(clock.clone())
+                .state_store(store.clone())
+                .shard_id(1)
+                .build()
+                .unwrap()
+        };
+
+        let generator = build();
+        let first = generator.next_id();
+        assert_eq!(store.load().unwrap(), Some(3_000));
+        // Ticks within the lease are not reserved again
+        clock.advance(1_000);
+        generator.next_id();
+        assert_eq!(store.load().unwrap(), Some(3_000));
+        clock.advance(1);
+        generator.next_id();
+        assert_eq!(store.load().unwrap(), Some(4_001));
+
+        let restarted = build().next_id();
+        assert!(restarted > first);
+        assert_eq!(store.load().unwrap(), Some(5_002));
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 4_002);
+    }
+}
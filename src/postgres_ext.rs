@@ -0,0 +1,52 @@
+use std::error::Error;
+
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::id::{Id, ParseIdError};
+
+/// Stored as `INT8` via [`Id::as_i64`], so IDs with the top bit set come
+/// out negative in SQL; use an [`i64`-safe](crate::Layout::new_i64_safe)
+/// layout if Postgres must order or compare them.
+impl<T> ToSql for Id<T> {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_i64().to_sql(ty, out)
+    }
+
+    accepts!(INT8);
+
+    to_sql_checked!();
+}
+
+impl<'a, T> FromSql<'a> for Id<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let value = i64::from_sql(ty, raw)?;
+        Id::from_i64(value).ok_or_else(|| ParseIdError::Zero.into())
+    }
+
+    accepts!(INT8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_postgres_round_trip() {
+        struct Order;
+
+        let id: Id<Order> = Id::from_u64(u64::MAX - 1).unwrap();
+        let mut buf = BytesMut::new();
+        id.to_sql_checked(&Type::INT8, &mut buf).unwrap();
+        assert_eq!(&buf[..], (-2i64).to_be_bytes());
+        assert_eq!(Id::<Order>::from_sql(&Type::INT8, &buf).unwrap(), id);
+
+        assert!(id.to_sql_checked(&Type::INT4, &mut buf).is_err());
+        assert!(!<Id as FromSql>::accepts(&Type::TEXT));
+        assert!(Id::<Order>::from_sql(&Type::INT8, &[0; 8]).is_err());
+    }
+}
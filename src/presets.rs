@@ -0,0 +1,274 @@
+//! Named layouts of well-known snowflake formats.
+//!
+//! ```
+//! use banuid::{presets, IdGenerator};
+//!
+//! let discord = IdGenerator::from_preset("discord").unwrap();
+//! let components = discord.layout().decode(175928847299117063);
+//! assert_eq!(components.timestamp_ms, 1462015105796);
+//! assert_eq!(presets::by_name("Discord"), Some(presets::DISCORD));
+//! ```
+
+use crate::layout::{FieldOrder, Layout, LayoutSpec, TimeUnit};
+use crate::IdGenerator;
+
+/// Twitter's Snowflake epoch, 2010-11-04 01:42:54.657 UTC
+pub(crate) const TWITTER_EPOCH: u64 = 1288834974657;
+
+/// Epoch from Instagram's `next_id` PL/pgSQL function, 2011-08-24 21:07:01.721 UTC
+pub(crate) const INSTAGRAM_EPOCH: u64 = 1314220021721;
+
+/// Sonyflake's default start time, 2014-09-01 00:00:00 UTC
+pub(crate) const SONYFLAKE_EPOCH: u64 = 1409529600000;
+
+/// Epoch of the short ID layout, 2025-01-01 00:00:00 UTC
+pub(crate) const SHORT_EPOCH: u64 = 1735689600000;
+
+/// Discord's epoch, 2015-01-01 00:00:00 UTC
+pub(crate) const DISCORD_EPOCH: u64 = 1420070400000;
+
+const SNOWFLAKE_DATACENTER_BITS: u8 = 5;
+
+/// banuid's own default layout.
+pub const BANUID: LayoutSpec = Layout::DEFAULT.spec();
+
+/// Twitter Snowflake, see [`Layout::SNOWFLAKE`].
+pub const TWITTER: LayoutSpec = Layout::SNOWFLAKE.spec();
+
+/// Discord snowflakes, see [`Layout::DISCORD`].
+pub const DISCORD: LayoutSpec = Layout::DISCORD.spec();
+
+/// Sonyflake, see [`Layout::SONYFLAKE`].
+pub const SONYFLAKE: LayoutSpec = Layout::SONYFLAKE.spec();
+
+/// Instagram sharded IDs, see [`Layout::INSTAGRAM`].
+pub const INSTAGRAM: LayoutSpec = Layout::INSTAGRAM.spec();
+
+/// Names accepted by [`by_name`] and [`IdGenerator::from_preset`].
+pub const NAMES: &[&str] = &[
+    "banuid",
+    "twitter",
+    "snowflake",
+    "discord",
+    "sonyflake",
+    "instagram",
+];
+
+/// Look up a preset by name, ignoring ASCII case.
+pub fn by_name(name: &str) -> Option<LayoutSpec> {
+    let spec = match name.to_ascii_lowercase().as_str() {
+        "banuid" => BANUID,
+        "twitter" | "snowflake" => TWITTER,
+        "discord" => DISCORD,
+        "sonyflake" => SONYFLAKE,
+        "instagram" => INSTAGRAM,
+        _ => return None,
+    };
+    Some(spec)
+}
+
+// Unwraps a preset layout, failing compilation if the preset is invalid
+const fn preset(layout: Result<Layout, crate::BuildError>) -> Layout {
+    match layout {
+        Ok(layout) => layout,
+        Err(_) => panic!("invalid preset layout"),
+    }
+}
+
+impl Layout {
+    /// Twitter Snowflake: sign bit, 41-bit ms timestamp since 2010-11-04,
+    /// 5-bit datacenter + 5-bit worker (the shard field) and a 12-bit sequence.
+    pub const SNOWFLAKE: Layout =
+        preset(preset(Layout::new(41, 10, 12)).split_shard(SNOWFLAKE_DATACENTER_BITS))
+            .with_epoch(TWITTER_EPOCH);
+
+    /// Discord: 42-bit ms timestamp since 2015-01-01, 5-bit internal worker +
+    /// 5-bit process (the shard field) and a 12-bit increment.
+    pub const DISCORD: Layout =
+        preset(preset(Layout::new(42, 10, 12)).split_shard(SNOWFLAKE_DATACENTER_BITS))
+            .with_epoch(DISCORD_EPOCH);
+
+    /// Sonyflake: sign bit, 39-bit timestamp in 10 ms units since 2014-09-01,
+    /// 8-bit sequence and a 16-bit machine ID in the low bits.
+    pub const SONYFLAKE: Layout = preset(Layout::new(39, 16, 8))
+        .with_epoch(SONYFLAKE_EPOCH)
+        .with_unit(TimeUnit::TenMillis)
+        .with_field_order(FieldOrder::SequenceFirst);
+
+    /// Instagram's sharded IDs: 41-bit ms timestamp since 2011-08-24,
+    /// 13-bit logical shard and a 10-bit sequence.
+    pub const INSTAGRAM: Layout = preset(Layout::new(41, 13, 10)).with_epoch(INSTAGRAM_EPOCH);
+
+    /// 53-bit layout for IDs consumed as JavaScript numbers: 41-bit ms
+    /// timestamp, 5-bit shard (32 shards) and a 7-bit sequence (128 IDs/ms).
+    pub const JS_SAFE: Layout = preset(Layout::new_js_safe(41, 5, 7));
+
+    /// 63-bit layout for signed BIGINT columns: 41-bit ms timestamp,
+    /// 12-bit shard and a 10-bit sequence, leaving the sign bit clear.
+    pub const I64_SAFE: Layout = preset(Layout::new_i64_safe(41, 12, 10));
+
+    /// 32-bit layout for [`ShortIdGenerator`](crate::ShortIdGenerator): 28-bit
+    /// second timestamp since 2025-01-01 (until mid 2033), 1-bit node and a
+    /// 3-bit sequence (8 IDs per second per node).
+    pub const SHORT: Layout = preset(Layout::new_short(28, 1, 3))
+        .with_epoch(SHORT_EPOCH)
+        .with_unit(TimeUnit::Seconds);
+}
+
+impl IdGenerator {
+    /// Generator using the named preset layout (see [`NAMES`]) and a shard ID
+    /// derived from the environment, or `None` for an unknown name.
+    pub fn from_preset(name: &str) -> Option<Self> {
+        let layout = by_name(name)?.to_layout().ok()?;
+        let shard_id = (crate::derive_shard_id() as u64 & layout.max_shard_id()) as u16;
+        Some(IdGenerator::from_parts(shard_id, layout))
+    }
+
+    /// Generator compatible with Twitter Snowflake IDs.
+    ///
+    /// Only the low 5 bits of `worker_id` and `datacenter_id` are used.
+    pub fn snowflake(worker_id: u16, datacenter_id: u16) -> Self {
+        let shard_id = Layout::SNOWFLAKE.shard_id_from(datacenter_id, worker_id);
+        IdGenerator::from_parts(shard_id, Layout::SNOWFLAKE)
+    }
+
+    /// Generator compatible with Sonyflake IDs.
+    ///
+    /// At most 256 IDs are issued per 10 ms tick; further calls wait for the next tick.
+    pub fn sonyflake(machine_id: u16) -> Self {
+        IdGenerator::from_parts(machine_id, Layout::SONYFLAKE)
+    }
+
+    /// Generator matching IDs minted by Instagram-style Postgres `next_id()` functions.
+    ///
+    /// `logical_shard` is the shard the row lives on (only the low 13 bits are
+    /// used), so IDs can be routed with [`Layout::extract_shard_id`]. Postgres
+    /// takes its sequence from a table sequence rather than a per-millisecond
+    /// counter, so give Rust writers their own logical shards to rule out overlap.
+    pub fn instagram(logical_shard: u16) -> Self {
+        let shard_id = (logical_shard as u64 & Layout::INSTAGRAM.max_shard_id()) as u16;
+        IdGenerator::from_parts(shard_id, Layout::INSTAGRAM)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snowflake_layout() {
+        let generator = IdGenerator::snowflake(3, 17);
+        let id = generator.next_id();
+
+        assert!(id < (1 << 63), "sign bit must stay clear");
+        assert_eq!((id >> 12) & 0x1F, 3, "worker bits");
+        assert_eq!((id >> 17) & 0x1F, 17, "datacenter bits");
+        assert_eq!(Layout::SNOWFLAKE.extract_worker(id), 3);
+        assert_eq!(Layout::SNOWFLAKE.extract_datacenter(id), 17);
+
+        let now = crate::current_time().as_millis() as u64;
+        let timestamp = (id >> 22) + TWITTER_EPOCH;
+        assert!(timestamp <= now && timestamp >= now - 1000);
+        assert_eq!(generator.layout().extract_timestamp(id), timestamp);
+    }
+
+    #[test]
+    fn test_snowflake_decodes_known_id() {
+        // A tweet ID from 2022-06-05
+        let id: u64 = 1_533_461_183_066_062_848;
+        assert_eq!(Layout::SNOWFLAKE.extract_timestamp(id), 1_654_440_611_031);
+    }
+
+    #[test]
+    fn test_discord_decodes_known_id() {
+        // Example from Discord's API reference
+        let id: u64 = 175928847299117063;
+        let layout = Layout::DISCORD;
+        assert_eq!(layout.extract_timestamp(id), 1462015105796);
+        assert_eq!(layout.extract_datacenter(id), 1);
+        assert_eq!(layout.extract_worker(id), 0);
+        assert_eq!(layout.extract_sequence(id), 7);
+    }
+
+    #[test]
+    fn test_presets_by_name() {
+        for name in NAMES {
+            let spec = by_name(name).unwrap();
+            assert_eq!(spec.to_layout().unwrap().spec(), spec);
+            let generator = IdGenerator::from_preset(name).unwrap();
+            assert_eq!(generator.layout().spec(), spec);
+            assert!(generator.next_id() > 0);
+        }
+        assert_eq!(by_name("TWITTER"), Some(TWITTER));
+        assert!(by_name("flickr").is_none());
+        assert!(IdGenerator::from_preset("flickr").is_none());
+    }
+
+    #[test]
+    fn test_sonyflake_layout() {
+        let generator = IdGenerator::sonyflake(0xCAFE);
+        let ids: Vec<u64> = (0..300).map(|_| generator.next_id()).collect();
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|&id| id & 0xFFFF == 0xCAFE));
+        assert_eq!(
+            generator.layout().extract_sequence(ids[1]),
+            ((ids[1] >> 16) & 0xFF) as u16
+        );
+
+        let now = crate::current_time().as_millis() as u64;
+        let timestamp = (ids[0] >> 24) * 10 + SONYFLAKE_EPOCH;
+        assert!(timestamp <= now && timestamp >= now - 1000);
+        assert_eq!(generator.layout().extract_timestamp(ids[0]), timestamp);
+    }
+
+    #[test]
+    fn test_instagram_layout() {
+        // Example from "Sharding & IDs at Instagram": shard 1341, sequence 5001 % 1024
+        let elapsed: u64 = 1387263000;
+        let id = (elapsed << 23) | (1341 << 10) | (5001 % 1024);
+        let layout = Layout::INSTAGRAM;
+
+        assert_eq!(layout.extract_timestamp(id), INSTAGRAM_EPOCH + elapsed);
+        assert_eq!(layout.extract_shard_id(id), 1341);
+        assert_eq!(layout.extract_sequence(id), 905);
+
+        let generator = IdGenerator::instagram(1341);
+        let id = generator.next_id();
+        assert_eq!(layout.extract_shard_id(id), 1341);
+        let now = crate::current_time().as_millis() as u64;
+        assert!(layout.extract_timestamp(id) <= now);
+    }
+
+    #[test]
+    fn test_js_safe_layout() {
+        let generator = IdGenerator::builder()
+            .js_safe()
+            .shard_id(31)
+            .build()
+            .unwrap();
+        assert_eq!(generator.layout(), &Layout::JS_SAFE);
+
+        const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+        for _ in 0..500 {
+            let id = generator.next_id();
+            assert!(id <= MAX_SAFE_INTEGER);
+            assert_eq!(id as f64 as u64, id);
+        }
+    }
+
+    #[test]
+    fn test_i64_safe_layout() {
+        let generator = IdGenerator::builder()
+            .i64_safe()
+            .shard_id(4095)
+            .build()
+            .unwrap();
+        assert_eq!(generator.layout(), &Layout::I64_SAFE);
+
+        // The timestamp field's top bit set, as it will be in ~2093
+        let late = Layout::I64_SAFE.encode(Layout::I64_SAFE.max_timestamp(), 4095, 1023);
+        assert_eq!(late, i64::MAX as u64);
+        assert!(crate::as_i64(generator.next_id()).unwrap() > 0);
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::BanuidError;
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Random 64-bit value, from OS entropy with the `getrandom` feature.
+///
+/// Without it, or if the OS source fails, hashes the time and a process-wide counter with std's SipHash.
+/// The keys are seeded from the OS once per thread, so values are distinct
+/// and well mixed but not cryptographically unpredictable; enable
+/// `getrandom` where guessing random bits must be infeasible.
+pub(crate) fn random_u64() -> u64 {
+    #[cfg(feature = "getrandom")]
+    if let Ok(value) = getrandom::u64() {
+        return value;
+    }
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.write_u64(crate::current_time().as_nanos() as u64);
+    hasher.finish()
+}
+
+/// Random 64-bit value from OS entropy, for the random sequence modes. The
+/// builder only allows those with the `getrandom` feature.
+pub(crate) fn secure_u64() -> Result<u64, BanuidError> {
+    #[cfg(feature = "getrandom")]
+    return getrandom::u64().map_err(|_| BanuidError::EntropyUnavailable);
+    #[cfg(not(feature = "getrandom"))]
+    Err(BanuidError::EntropyUnavailable)
+}
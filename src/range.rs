@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+impl Layout {
+    /// Smallest ID this layout can produce during the millisecond `timestamp_ms`
+    /// (since the Unix epoch), for `WHERE id BETWEEN min AND max` range queries.
+    ///
+    /// Times outside the timestamp field's range clamp to its first or last tick.
+    pub fn min_id_at(&self, timestamp_ms: u64) -> u64 {
+        let tick = self
+            .checked_ticks_at(Duration::from_millis(timestamp_ms))
+            .unwrap_or(0)
+            .min(self.max_timestamp());
+        let version = if self.version_bits() == 0 {
+            0
+        } else {
+            (self.version() as u64) << self.version_shift()
+        };
+        version | (tick << self.timestamp_shift())
+    }
+
+    /// Largest ID this layout can produce during the millisecond `timestamp_ms`.
+    pub fn max_id_at(&self, timestamp_ms: u64) -> u64 {
+        let below_timestamp = u64::MAX
+            .checked_shr(64 - self.timestamp_shift())
+            .unwrap_or(0);
+        self.min_id_at(timestamp_ms) | below_timestamp
+    }
+}
+
+impl Layout {
+    /// Split `[start_ms, end_ms)` (milliseconds since the Unix epoch) into
+    /// buckets of `bucket` length, yielding `(bucket_start_ms, min_id, max_id)`
+    /// for each, e.g. to prune partitions or scan a day at a time.
+    ///
+    /// The last bucket is cut short at `end_ms`.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use banuid::Layout;
+    ///
+    /// let day = Duration::from_secs(86_400);
+    /// let start = 1_704_067_200_000; // 2024-01-01
+    /// let buckets: Vec<_> = Layout::DEFAULT.buckets(start, start + 7 * 86_400_000, day).collect();
+    /// assert_eq!(buckets.len(), 7);
+    /// assert_eq!(buckets[1].0, start + 86_400_000);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bucket` is shorter than a millisecond.
+    pub fn buckets(&self, start_ms: u64, end_ms: u64, bucket: Duration) -> IdBuckets {
+        let step = bucket.as_millis().min(u64::MAX as u128) as u64;
+        assert!(step > 0, "bucket must be at least one millisecond");
+        IdBuckets {
+            layout: *self,
+            next: start_ms,
+            end: end_ms,
+            step,
+        }
+    }
+}
+
+/// Iterator over time buckets and their ID ranges, see [`Layout::buckets`].
+#[derive(Debug, Clone)]
+pub struct IdBuckets {
+    layout: Layout,
+    next: u64,
+    end: u64,
+    step: u64,
+}
+
+impl Iterator for IdBuckets {
+    type Item = (u64, u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        let start = self.next;
+        let end = start.saturating_add(self.step).min(self.end);
+        self.next = end;
+        Some((
+            start,
+            self.layout.min_id_at(start),
+            self.layout.max_id_at(end - 1),
+        ))
+    }
+}
+
+impl IdGenerator {
+    /// Smallest default-layout ID for the millisecond `timestamp_ms`, see [`Layout::min_id_at`].
+    pub fn min_id_at(timestamp_ms: u64) -> u64 {
+        Layout::DEFAULT.min_id_at(timestamp_ms)
+    }
+
+    /// Largest default-layout ID for the millisecond `timestamp_ms`, see [`Layout::max_id_at`].
+    pub fn max_id_at(timestamp_ms: u64) -> u64 {
+        Layout::DEFAULT.max_id_at(timestamp_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary_ids() {
+        let layout = Layout::DEFAULT;
+        let at = crate::CUSTOM_EPOCH + 5_000;
+        let min = IdGenerator::min_id_at(at);
+        let max = IdGenerator::max_id_at(at);
+
+        assert_eq!(min, layout.encode(5_000, 0, 0));
+        assert_eq!(
+            max,
+            layout.encode(5_000, layout.max_shard_id(), layout.max_sequence())
+        );
+        assert_eq!(max + 1, IdGenerator::min_id_at(at + 1));
+        assert_eq!(layout.min_id_at(0), 0);
+        assert_eq!(layout.max_id_at(u64::MAX), u64::MAX);
+
+        let id = IdGenerator::new().next_id();
+        let created = layout.extract_timestamp(id);
+        assert!((layout.min_id_at(created)..=layout.max_id_at(created)).contains(&id));
+    }
+
+    #[test]
+    fn test_buckets() {
+        let layout = Layout::DEFAULT;
+        let start = crate::CUSTOM_EPOCH + 1_000;
+        let buckets: Vec<_> = layout
+            .buckets(start, start + 2_500, Duration::from_secs(1))
+            .collect();
+
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(
+            buckets[0],
+            (
+                start,
+                layout.min_id_at(start),
+                layout.max_id_at(start + 999)
+            )
+        );
+        assert_eq!(buckets[1].1, buckets[0].2 + 1);
+        assert_eq!(buckets[2].0, start + 2_000);
+        assert_eq!(buckets[2].2, layout.max_id_at(start + 2_499));
+        assert_eq!(
+            layout.buckets(start, start, Duration::from_secs(1)).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_boundary_ids_versioned_and_coarse() {
+        let layout = Layout::DEFAULT
+            .with_unit(crate::TimeUnit::Seconds)
+            .with_version(2, 1)
+            .unwrap();
+        let at = crate::CUSTOM_EPOCH + 3_250;
+        assert_eq!(layout.min_id_at(at), layout.encode(3, 0, 0));
+        assert_eq!(
+            layout.max_id_at(at),
+            layout.max_id_at(crate::CUSTOM_EPOCH + 3_999)
+        );
+        assert_eq!(layout.extract_version(layout.max_id_at(at)), 1);
+    }
+}
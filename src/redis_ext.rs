@@ -0,0 +1,114 @@
+use redis::{FromRedisValue, RedisResult, RedisWrite, ToRedisArgs, Value};
+
+use crate::id::{Id, ParseIdError};
+use crate::layout::Layout;
+
+/// Written as a decimal string, so IDs work as keys, members and hash fields.
+impl<T> ToRedisArgs for Id<T> {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        self.as_u64().write_redis_args(out)
+    }
+}
+
+/// Accepts integer replies and decimal strings.
+impl<T> FromRedisValue for Id<T> {
+    fn from_redis_value(value: &Value) -> RedisResult<Self> {
+        let value = u64::from_redis_value(value)?;
+        Id::from_u64(value).ok_or_else(|| {
+            (
+                redis::ErrorKind::TypeError,
+                "invalid ID",
+                ParseIdError::Zero.to_string(),
+            )
+                .into()
+        })
+    }
+}
+
+impl Layout {
+    /// A Redis stream entry ID (`<ms>-<seq>`) for `id`, for `XADD` with
+    /// explicit IDs.
+    ///
+    /// The first part is the ID's timestamp in milliseconds and the second
+    /// the bits below the timestamp, so entry IDs order like the IDs.
+    /// Version bits are dropped.
+    pub fn redis_stream_id(&self, id: u64) -> String {
+        let below_timestamp = u64::MAX
+            .checked_shr(64 - self.timestamp_shift())
+            .unwrap_or(0);
+        let low = id & below_timestamp;
+        format!("{}-{}", self.extract_timestamp(id), low)
+    }
+
+    /// A sorted-set score for `id`: its timestamp in milliseconds since the
+    /// Unix epoch.
+    ///
+    /// Scores are `f64`, which holds integers exactly only up to 2^53, so
+    /// full IDs would collide. IDs minted in the same millisecond share a
+    /// score and Redis orders them by member, which matches ID order for
+    /// members of equal length. [`js-safe`](Layout::new_js_safe) IDs can be
+    /// used as scores directly.
+    pub fn redis_score(&self, id: u64) -> f64 {
+        self.extract_timestamp(id) as f64
+    }
+}
+
+impl<T> Id<T> {
+    /// The ID as a Redis stream entry ID, see [`Layout::redis_stream_id`].
+    pub fn to_redis_stream_id(&self) -> String {
+        Layout::DEFAULT.redis_stream_id(self.as_u64())
+    }
+
+    /// The ID's sorted-set score, see [`Layout::redis_score`].
+    pub fn redis_score(&self) -> f64 {
+        Layout::DEFAULT.redis_score(self.as_u64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redis_args_and_values() {
+        struct Order;
+
+        let id: Id<Order> = Id::from_u64(u64::MAX).unwrap();
+        assert_eq!(id.to_redis_args(), vec![b"18446744073709551615".to_vec()]);
+
+        let reply = Value::BulkString(b"18446744073709551615".to_vec());
+        assert_eq!(Id::<Order>::from_redis_value(&reply).unwrap(), id);
+        assert_eq!(
+            Id::<Order>::from_redis_value(&Value::Int(7))
+                .unwrap()
+                .as_u64(),
+            7
+        );
+        assert!(Id::<Order>::from_redis_value(&Value::Int(0)).is_err());
+        assert!(Id::<Order>::from_redis_value(&Value::Nil).is_err());
+    }
+
+    #[test]
+    fn test_redis_stream_id_and_score() {
+        let layout = Layout::DEFAULT;
+        let id = layout.encode(1_000, 3, 7);
+        let timestamp = layout.extract_timestamp(id);
+        let low = (3 << layout.sequence_bits()) | 7;
+        assert_eq!(layout.redis_stream_id(id), format!("{}-{}", timestamp, low));
+        assert_eq!(layout.redis_score(id), timestamp as f64);
+
+        let later = layout.encode(1_000, 3, 8);
+        let id: Id = Id::from_u64(id).unwrap();
+        assert_eq!(id.redis_score(), layout.redis_score(later));
+        assert_eq!(
+            Id::<()>::from_u64(later).unwrap().to_redis_stream_id(),
+            format!("{}-{}", timestamp, low + 1)
+        );
+
+        let untimed = Layout::new_short(0, 16, 16).unwrap();
+        assert_eq!(
+            untimed.redis_stream_id(0xFFFF_0005),
+            format!("{}-{}", untimed.epoch(), 0xFFFF_0005u64)
+        );
+    }
+}
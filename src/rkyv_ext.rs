@@ -0,0 +1,61 @@
+use std::num::NonZeroU64;
+
+use rkyv::primitive::ArchivedNonZeroU64;
+use rkyv::rancor::Fallible;
+use rkyv::{Archive, Deserialize, Place, Serialize};
+
+use crate::id::Id;
+
+/// Archives as a plain `NonZeroU64`, whatever the marker type,
+/// so archived IDs can be read in place without deserializing.
+impl<T> Archive for Id<T> {
+    type Archived = ArchivedNonZeroU64;
+    type Resolver = ();
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        self.as_non_zero().resolve(resolver, out)
+    }
+}
+
+impl<T, S: Fallible + ?Sized> Serialize<S> for Id<T> {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.as_non_zero().serialize(serializer)
+    }
+}
+
+impl<T, D: Fallible + ?Sized> Deserialize<Id<T>, D> for ArchivedNonZeroU64 {
+    fn deserialize(&self, deserializer: &mut D) -> Result<Id<T>, D::Error> {
+        Deserialize::<NonZeroU64, D>::deserialize(self, deserializer).map(Id::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use crate::{Id, Id128, Ulid};
+
+    #[test]
+    fn test_rkyv_round_trip() {
+        struct Order;
+
+        let ids: Vec<Id<Order>> = (1..=3).map(|n| Id::from_u64(n << 40).unwrap()).collect();
+        let bytes = rkyv::to_bytes::<Error>(&ids).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec<Id<Order>>>, Error>(&bytes).unwrap();
+        assert_eq!(archived[2].get(), 3 << 40);
+        let restored: Vec<Id<Order>> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(restored, ids);
+
+        // Zero is rejected when validating the archive
+        let zero = rkyv::to_bytes::<Error>(&0u64).unwrap();
+        assert!(rkyv::access::<rkyv::Archived<Id>, Error>(&zero).is_err());
+
+        let ulid = Ulid::new(1_704_067_200_000, 42);
+        let bytes = rkyv::to_bytes::<Error>(&ulid).unwrap();
+        assert_eq!(rkyv::from_bytes::<Ulid, Error>(&bytes).unwrap(), ulid);
+
+        let id128 = Id128::from_u128(7 << 80);
+        let bytes = rkyv::to_bytes::<Error>(&id128).unwrap();
+        assert_eq!(rkyv::from_bytes::<Id128, Error>(&bytes).unwrap(), id128);
+    }
+}
@@ -0,0 +1,57 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::id::{Id, ParseIdError};
+
+/// Stored as `INTEGER` via [`Id::as_i64`], so IDs with the top bit set come
+/// out negative in SQL; use an [`i64`-safe](crate::Layout::new_i64_safe)
+/// layout if SQLite must order or compare them.
+impl<T> ToSql for Id<T> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.as_i64().into())
+    }
+}
+
+impl<T> FromSql for Id<T> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let value = i64::column_result(value)?;
+        Id::from_i64(value).ok_or_else(|| FromSqlError::Other(Box::new(ParseIdError::Zero)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use crate::Id;
+
+    #[test]
+    fn test_rusqlite_round_trip() {
+        struct Order;
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE orders (id INTEGER PRIMARY KEY)", [])
+            .unwrap();
+        let ids: Vec<Id<Order>> = [1 << 40, u64::MAX]
+            .into_iter()
+            .map(|n| Id::from_u64(n).unwrap())
+            .collect();
+        for id in &ids {
+            conn.execute("INSERT INTO orders (id) VALUES (?1)", [id])
+                .unwrap();
+        }
+
+        let stored: i64 = conn
+            .query_row("SELECT max(id) FROM orders", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, 1 << 40);
+        let restored: Id<Order> = conn
+            .query_row("SELECT id FROM orders WHERE id = ?1", [ids[1]], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(restored, ids[1]);
+        assert!(conn
+            .query_row("SELECT 0", [], |row| row.get::<_, Id>(0))
+            .is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::alphabet::Alphabet;
+use crate::id::ParseIdError;
+use crate::obfuscate::{deobfuscate, fnv1a, mix64, obfuscate};
+
+const BASE62_SYMBOLS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Salted, reversible encoding of IDs as opaque 11-character slugs, in the
+/// spirit of Hashids, so public URLs don't expose the raw sortable ID.
+///
+/// The salt keys an [`obfuscate`](crate::obfuscate) permutation and shuffles
+/// the base62 alphabet, so each deployment's slugs look unrelated to any
+/// other's. Keep the salt stable: changing it invalidates issued slugs.
+///
+/// ```
+/// use banuid::SaltedEncoder;
+///
+/// let encoder = SaltedEncoder::new("my deployment salt");
+/// let id = banuid::generate();
+/// let slug = encoder.encode(id);
+/// assert_eq!(slug.len(), 11);
+/// assert_eq!(encoder.decode(&slug), Ok(id));
+/// assert_ne!(SaltedEncoder::new("other salt").decode(&slug), Ok(id));
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct SaltedEncoder {
+    key: u64,
+    alphabet: Alphabet,
+}
+
+impl SaltedEncoder {
+    pub fn new(salt: &str) -> Self {
+        let key = mix64(fnv1a(salt.as_bytes()));
+
+        // Fisher-Yates shuffle driven by a SplitMix64 stream from the key
+        let mut symbols = BASE62_SYMBOLS.as_bytes().to_vec();
+        let mut state = key;
+        for i in (1..symbols.len()).rev() {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let j = (mix64(state) % (i as u64 + 1)) as usize;
+            symbols.swap(i, j);
+        }
+        // A permutation of the base62 symbols is always a valid alphabet
+        let alphabet = Alphabet::new(std::str::from_utf8(&symbols).unwrap()).unwrap();
+
+        SaltedEncoder { key, alphabet }
+    }
+
+    pub fn encode(&self, id: u64) -> String {
+        self.alphabet.encode(obfuscate(id, self.key))
+    }
+
+    /// Recover the ID from a slug made by [`encode`](Self::encode) with the
+    /// same salt. A slug from another salt decodes to an unrelated ID rather
+    /// than failing, so look the result up before trusting it.
+    pub fn decode(&self, slug: &str) -> Result<u64, ParseIdError> {
+        Ok(deobfuscate(self.alphabet.decode(slug)?, self.key))
+    }
+}
+
+// Keeps the key and shuffled alphabet out of logs
+impl std::fmt::Debug for SaltedEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SaltedEncoder(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_salted_encoder() {
+        let encoder = SaltedEncoder::new("salt");
+        for id in [0, 1, 2, 1 << 40, u64::MAX] {
+            assert_eq!(encoder.decode(&encoder.encode(id)), Ok(id));
+        }
+        // Slugs are stable for a salt
+        assert_eq!(SaltedEncoder::new("salt"), encoder);
+        assert_ne!(SaltedEncoder::new("pepper").encode(1), encoder.encode(1));
+
+        // Consecutive IDs don't give slugs differing only at the end
+        assert_ne!(encoder.encode(100)[..10], encoder.encode(101)[..10]);
+        assert_eq!(encoder.decode("a-"), Err(ParseIdError::InvalidChar('-')));
+        assert_eq!(format!("{:?}", encoder), "SaltedEncoder(..)");
+    }
+}
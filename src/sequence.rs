@@ -0,0 +1,22 @@
+/// How the sequence field of each ID is filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SequenceMode {
+    /// A per-tick counter starting at 0, so IDs from one generator are strictly
+    /// increasing. Generation waits for the next tick once the counter is exhausted.
+    #[default]
+    Counter,
+    /// A per-tick counter starting at a random offset in the lower half of the
+    /// sequence range, so the low bits do not reveal per-tick volume. IDs stay
+    /// strictly increasing, at the cost of up to half of each tick's capacity.
+    RandomStart,
+    /// A per-tick counter in the high bits of the sequence field, with this
+    /// many random bits below it, so consecutive IDs cannot be guessed by
+    /// incrementing. IDs stay strictly increasing, but each tick holds
+    /// `2^bits` times fewer of them. The bits come from OS entropy, so this
+    /// mode needs the `getrandom` feature.
+    RandomLowBits(u8),
+    /// Random bits, UUIDv7-style. IDs within a tick are unordered and reveal
+    /// nothing about per-tick volume; uniqueness within a tick is probabilistic.
+    /// Needs the `getrandom` feature.
+    Random,
+}
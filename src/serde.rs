@@ -0,0 +1,244 @@
+//! Wire representations for [`Id`] fields, selected with `#[serde(with)]`.
+//!
+//! [`Id`] serializes as an integer by default. JavaScript numbers lose
+//! precision above 2^53, so JSON APIs consumed from browsers should send IDs
+//! as decimal strings instead:
+//!
+//! ```
+//! use banuid::Id;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Order {
+//!     #[serde(with = "banuid::serde::string")]
+//!     id: Id,
+//! }
+//!
+//! let order = Order { id: Id::from_u64(1 << 60).unwrap() };
+//! let json = serde_json::to_string(&order).unwrap();
+//! assert_eq!(json, r#"{"id":"1152921504606846976"}"#);
+//! ```
+//!
+//! The modules also work on plain `u64` fields, so existing structs can
+//! switch representation without changing field types. [`IdStr`] and
+//! [`IdNum`] do the same inside containers.
+//!
+//! In human-readable formats both representations accept either form when
+//! deserializing, so producers and consumers can switch independently.
+
+use std::fmt;
+
+use ::serde::de::{self, Unexpected, Visitor};
+use ::serde::{Deserializer, Serializer};
+
+use crate::id::{Id, ParseIdError};
+
+/// Serialize IDs as decimal strings.
+pub mod string {
+    use super::*;
+
+    pub fn serialize<V: IdValue, S: Serializer>(id: &V, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&id.to_u64())
+    }
+
+    pub fn deserialize<'de, V: IdValue, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<V, D::Error> {
+        let value = if deserializer.is_human_readable() {
+            deserializer.deserialize_any(IdVisitor)?
+        } else {
+            deserializer.deserialize_str(IdVisitor)?
+        };
+        V::from_u64(value)
+    }
+}
+
+/// Serialize IDs as integers, the default for [`Id`].
+pub mod number {
+    use super::*;
+
+    pub fn serialize<V: IdValue, S: Serializer>(id: &V, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(id.to_u64())
+    }
+
+    pub fn deserialize<'de, V: IdValue, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<V, D::Error> {
+        let value = if deserializer.is_human_readable() {
+            deserializer.deserialize_any(IdVisitor)?
+        } else {
+            deserializer.deserialize_u64(IdVisitor)?
+        };
+        V::from_u64(value)
+    }
+}
+
+/// Field types the [`string`] and [`number`] modules work with: `u64` and
+/// [`Id`].
+pub trait IdValue: Sized + private::Sealed {
+    #[doc(hidden)]
+    fn to_u64(&self) -> u64;
+    #[doc(hidden)]
+    fn from_u64<E: de::Error>(value: u64) -> Result<Self, E>;
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for u64 {}
+    impl<T> Sealed for crate::id::Id<T> {}
+}
+
+impl IdValue for u64 {
+    fn to_u64(&self) -> u64 {
+        *self
+    }
+
+    fn from_u64<E: de::Error>(value: u64) -> Result<Self, E> {
+        Ok(value)
+    }
+}
+
+impl<T> IdValue for Id<T> {
+    fn to_u64(&self) -> u64 {
+        self.as_u64()
+    }
+
+    fn from_u64<E: de::Error>(value: u64) -> Result<Self, E> {
+        Id::from_u64(value).ok_or_else(|| E::custom(ParseIdError::Zero))
+    }
+}
+
+/// A `u64` ID that serializes as a decimal string, for containers such as
+/// `Vec<IdStr>` where `#[serde(with)]` can't reach the elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct IdStr(pub u64);
+
+/// A `u64` ID that serializes as an integer but, like [`IdStr`], accepts
+/// either form from human-readable formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct IdNum(pub u64);
+
+macro_rules! id_wrapper {
+    ($name:ident, $repr:ident) => {
+        impl ::serde::Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $repr::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                $repr::deserialize(deserializer).map($name)
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+id_wrapper!(IdStr, string);
+id_wrapper!(IdNum, number);
+
+// Accepts an integer or a decimal string
+struct IdVisitor;
+
+impl Visitor<'_> for IdVisitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an ID as an integer or decimal string")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        #[serde(with = "string")]
+        id: Id,
+        #[serde(with = "number")]
+        parent: Id,
+    }
+
+    #[test]
+    fn test_id_representations() {
+        let event = Event {
+            id: Id::from_u64(u64::MAX).unwrap(),
+            parent: Id::from_u64(7).unwrap(),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(json, r#"{"id":"18446744073709551615","parent":7}"#);
+        assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), event);
+
+        // Either form is accepted on input
+        let swapped = r#"{"id":18446744073709551615,"parent":"7"}"#;
+        assert_eq!(serde_json::from_str::<Event>(swapped).unwrap(), event);
+        assert_eq!(serde_json::from_str::<Id>(r#""42""#).unwrap().as_u64(), 42);
+
+        assert!(serde_json::from_str::<Event>(r#"{"id":"0","parent":7}"#).is_err());
+        assert!(serde_json::from_str::<Event>(r#"{"id":"x","parent":7}"#).is_err());
+        assert!(serde_json::from_str::<Event>(r#"{"id":1,"parent":-7}"#).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Batch {
+        #[serde(with = "string")]
+        owner: u64,
+        ids: Vec<IdStr>,
+        counts: Vec<IdNum>,
+    }
+
+    #[test]
+    fn test_u64_representations() {
+        let batch = Batch {
+            owner: 0,
+            ids: vec![IdStr(1 << 60), IdStr::from(2)],
+            counts: vec![IdNum(3)],
+        };
+        let json = serde_json::to_string(&batch).unwrap();
+        assert_eq!(
+            json,
+            r#"{"owner":"0","ids":["1152921504606846976","2"],"counts":[3]}"#
+        );
+        assert_eq!(serde_json::from_str::<Batch>(&json).unwrap(), batch);
+        assert_eq!(
+            serde_json::from_str::<Vec<IdNum>>(r#"["3",4]"#).unwrap(),
+            vec![IdNum(3), IdNum(4)]
+        );
+        assert_eq!(IdStr(5).to_string(), "5");
+        assert_eq!(u64::from(IdNum(6)), 6);
+    }
+}
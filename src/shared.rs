@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use crate::builder::BuildError;
+use crate::error::BanuidError;
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+/// A cheaply cloneable handle to one generator, for sharing across threads
+/// and tasks without wrapping it in an `Arc` by hand.
+///
+/// All clones draw from the same sequence, so IDs stay unique across them.
+///
+/// ```
+/// use banuid::{IdGenerator, SharedGenerator};
+/// use std::thread;
+///
+/// let generator = SharedGenerator::new(IdGenerator::with_shard_id(7));
+/// let handle = generator.clone();
+/// let other = thread::spawn(move || handle.next_id()).join().unwrap();
+/// assert_ne!(generator.next_id(), other);
+/// ```
+#[derive(Clone)]
+pub struct SharedGenerator {
+    inner: Arc<IdGenerator>,
+}
+
+impl SharedGenerator {
+    pub fn new(generator: IdGenerator) -> Self {
+        SharedGenerator {
+            inner: Arc::new(generator),
+        }
+    }
+
+    /// Generate an ID using this instance
+    pub fn generate(&self) -> u64 {
+        self.inner.next_id()
+    }
+
+    /// See [`IdGenerator::next_id`].
+    pub fn next_id(&self) -> u64 {
+        self.inner.next_id()
+    }
+
+    /// See [`IdGenerator::try_next_id`].
+    pub fn try_next_id(&self) -> Result<u64, BanuidError> {
+        self.inner.try_next_id()
+    }
+
+    /// See [`IdGenerator::next_id_nonblocking`].
+    pub fn next_id_nonblocking(&self) -> Result<u64, BanuidError> {
+        self.inner.next_id_nonblocking()
+    }
+
+    /// See [`IdGenerator::next_id_with_expiry_class`].
+    pub fn next_id_with_expiry_class(&self, class: u8) -> u64 {
+        self.inner.next_id_with_expiry_class(class)
+    }
+
+    /// See [`IdGenerator::observe`].
+    pub fn observe(&self, remote_id: u64) {
+        self.inner.observe(remote_id)
+    }
+
+    /// See [`IdGenerator::rotate_shard`].
+    pub fn rotate_shard(&self, shard_id: u16) -> Result<(), BuildError> {
+        self.inner.rotate_shard(shard_id)
+    }
+
+    pub fn shard_id(&self) -> u16 {
+        self.inner.shard_id()
+    }
+
+    pub fn layout(&self) -> &Layout {
+        self.inner.layout()
+    }
+
+    /// The underlying generator, for APIs not mirrored here.
+    pub fn as_generator(&self) -> &IdGenerator {
+        &self.inner
+    }
+}
+
+impl From<IdGenerator> for SharedGenerator {
+    fn from(generator: IdGenerator) -> Self {
+        Self::new(generator)
+    }
+}
+
+impl Default for SharedGenerator {
+    fn default() -> Self {
+        Self::new(IdGenerator::new())
+    }
+}
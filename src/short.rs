@@ -0,0 +1,132 @@
+use crate::builder::BuildError;
+use crate::error::BanuidError;
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+/// Generates 32-bit IDs for embedded and protocol use where 64 bits is too wide.
+///
+/// Short IDs trade almost everything for size: with [`Layout::SHORT`] a node
+/// issues at most [`max_ids_per_second`](Layout::max_ids_per_second) IDs per
+/// second, only [`max_shards`](Layout::max_shards) nodes can coexist, and the
+/// timestamp runs out at [`timestamp_exhaustion_date`](Layout::timestamp_exhaustion_date),
+/// after which generation fails with [`BanuidError::TimestampOverflow`].
+/// Check these against your workload before use.
+///
+/// ```
+/// use banuid::{Layout, ShortIdGenerator};
+///
+/// let generator = ShortIdGenerator::with_layout(Layout::SHORT, 1).unwrap();
+/// let id: u32 = generator.next_id();
+/// assert_eq!(generator.layout().extract_shard_id(id as u64), 1);
+/// assert_eq!(generator.layout().max_ids_per_second(), 8);
+/// ```
+pub struct ShortIdGenerator {
+    inner: IdGenerator,
+}
+
+impl ShortIdGenerator {
+    /// Generator using [`Layout::SHORT`] and a node ID derived from the environment.
+    pub fn new() -> Self {
+        let layout = Layout::SHORT;
+        let node_id = (crate::derive_shard_id() as u64 & layout.max_shard_id()) as u16;
+        ShortIdGenerator {
+            inner: IdGenerator::from_parts(node_id, layout),
+        }
+    }
+
+    /// Generator using a custom layout of at most 32 bits, see [`Layout::new_short`].
+    pub fn with_layout(layout: Layout, node_id: u16) -> Result<Self, BuildError> {
+        let total = layout.total_bits() as u32;
+        if total > 32 {
+            return Err(BuildError::NotU32Safe { total });
+        }
+        if node_id as u64 > layout.max_shard_id() {
+            return Err(BuildError::ShardIdOutOfRange {
+                shard_id: node_id,
+                max: layout.max_shard_id() as u16,
+            });
+        }
+        Ok(ShortIdGenerator {
+            inner: IdGenerator::from_parts(node_id, layout),
+        })
+    }
+
+    /// Next ID, waiting for the next tick once the sequence is exhausted.
+    ///
+    /// # Panics
+    ///
+    /// Panics once the timestamp field is exhausted, or on the other errors
+    /// [`try_next_id`](Self::try_next_id) returns.
+    pub fn next_id(&self) -> u32 {
+        self.inner.next_id() as u32
+    }
+
+    /// Next ID, failing with [`BanuidError::TimestampOverflow`] once the
+    /// timestamp field is exhausted instead of panicking.
+    pub fn try_next_id(&self) -> Result<u32, BanuidError> {
+        self.inner.try_next_id().map(|id| id as u32)
+    }
+
+    pub fn node_id(&self) -> u16 {
+        self.inner.shard_id()
+    }
+
+    pub fn layout(&self) -> &Layout {
+        self.inner.layout()
+    }
+}
+
+impl Default for ShortIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TimeUnit;
+
+    #[test]
+    fn test_short_ids() {
+        let layout = Layout::new_short(20, 2, 10)
+            .unwrap()
+            .with_unit(TimeUnit::Seconds)
+            .with_epoch(crate::current_time().as_millis() as u64 - 60_000);
+        let generator = ShortIdGenerator::with_layout(layout, 3).unwrap();
+
+        let ids: Vec<u32> = (0..100).map(|_| generator.next_id()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids
+            .iter()
+            .all(|&id| layout.extract_shard_id(id as u64) == 3));
+        assert!(layout.extract_raw_timestamp(ids[0] as u64) >= 60);
+    }
+
+    #[test]
+    fn test_short_timestamp_exhausted() {
+        // 6 bits of seconds ran out a minute after the epoch
+        let layout = Layout::new_short(6, 2, 10)
+            .unwrap()
+            .with_unit(TimeUnit::Seconds)
+            .with_epoch(crate::current_time().as_millis() as u64 - 120_000);
+        let generator = ShortIdGenerator::with_layout(layout, 1).unwrap();
+        assert_eq!(generator.try_next_id(), Err(BanuidError::TimestampOverflow));
+    }
+
+    #[test]
+    fn test_short_layout_limits() {
+        assert_eq!(Layout::SHORT.total_bits(), 32);
+        assert_eq!(
+            Layout::new_short(24, 4, 5),
+            Err(BuildError::NotU32Safe { total: 33 })
+        );
+        assert!(ShortIdGenerator::with_layout(Layout::DEFAULT, 0).is_err());
+        assert!(ShortIdGenerator::with_layout(Layout::SHORT, 2).is_err());
+
+        let exhausted = Layout::SHORT.timestamp_exhaustion_date().unwrap();
+        let now = std::time::UNIX_EPOCH + crate::current_time();
+        assert!(exhausted > now);
+        assert!(ShortIdGenerator::new().node_id() <= 1);
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::layout::Layout;
+
+/// Estimates how far each peer shard's clock runs ahead of (positive) or
+/// behind (negative) the local clock, from IDs received from those shards.
+///
+/// Each ID yields `embedded timestamp - receipt time`, which is the true skew
+/// minus the delay between minting and receipt. The estimate per shard is the
+/// largest such sample, so it converges on the skew as the fastest-delivered
+/// IDs are seen, and errs towards reporting peers as behind.
+///
+/// ```
+/// use banuid::{Layout, SkewEstimator};
+/// use std::time::Duration;
+///
+/// let mut estimator = SkewEstimator::new(Layout::DEFAULT);
+/// let id = Layout::DEFAULT.encode(10_250, 7, 0); // minted by shard 7
+/// estimator.observe_at(id, Duration::from_millis(1_704_067_210_000));
+/// assert_eq!(estimator.estimate(7), Some(250));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkewEstimator {
+    layout: Layout,
+    offsets: HashMap<u16, i64>,
+}
+
+impl SkewEstimator {
+    pub fn new(layout: Layout) -> Self {
+        SkewEstimator {
+            layout,
+            offsets: HashMap::new(),
+        }
+    }
+
+    /// Record `id` as received now, by the system clock.
+    pub fn observe(&mut self, id: u64) {
+        self.observe_at(id, crate::current_time());
+    }
+
+    /// Record `id` as received at `received` (time since the Unix epoch).
+    pub fn observe_at(&mut self, id: u64, received: Duration) {
+        let offset = self.layout.extract_timestamp(id) as i64 - received.as_millis() as i64;
+        let shard_id = self.layout.extract_shard_id(id);
+        self.offsets
+            .entry(shard_id)
+            .and_modify(|best| *best = (*best).max(offset))
+            .or_insert(offset);
+    }
+
+    /// Estimated skew of `shard_id` in milliseconds, or `None` before any of its IDs were seen.
+    pub fn estimate(&self, shard_id: u16) -> Option<i64> {
+        self.offsets.get(&shard_id).copied()
+    }
+
+    /// Estimates for every observed shard, ordered by shard ID.
+    pub fn estimates(&self) -> Vec<(u16, i64)> {
+        let mut estimates: Vec<_> = self.offsets.iter().map(|(&s, &o)| (s, o)).collect();
+        estimates.sort_unstable();
+        estimates
+    }
+
+    /// Largest absolute skew across observed shards, for a single fleet health metric.
+    pub fn max_abs_skew(&self) -> Option<u64> {
+        self.offsets
+            .values()
+            .map(|offset| offset.unsigned_abs())
+            .max()
+    }
+
+    /// Forget all samples, e.g. to start a new measurement window.
+    pub fn reset(&mut self) {
+        self.offsets.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skew_estimation() {
+        let layout = Layout::DEFAULT;
+        let received = |ms: u64| Duration::from_millis(crate::CUSTOM_EPOCH + ms);
+        let mut estimator = SkewEstimator::new(layout);
+
+        // Shard 1 runs 100 ms ahead; deliveries take 5 to 40 ms
+        estimator.observe_at(layout.encode(1_100, 1, 0), received(1_040));
+        estimator.observe_at(layout.encode(2_100, 1, 0), received(2_005));
+        // Shard 2 runs 50 ms behind
+        estimator.observe_at(layout.encode(950, 2, 0), received(1_010));
+
+        assert_eq!(estimator.estimate(1), Some(95));
+        assert_eq!(estimator.estimate(2), Some(-60));
+        assert_eq!(estimator.estimate(3), None);
+        assert_eq!(estimator.estimates(), vec![(1, 95), (2, -60)]);
+        assert_eq!(estimator.max_abs_skew(), Some(95));
+
+        estimator.reset();
+        assert!(estimator.estimates().is_empty());
+    }
+}
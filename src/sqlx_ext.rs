@@ -0,0 +1,48 @@
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Type};
+
+use crate::id::{Id, ParseIdError};
+
+/// Stored as `BIGINT` via [`Id::as_i64`], so IDs with the top bit set come
+/// out negative in SQL; use an [`i64`-safe](crate::Layout::new_i64_safe)
+/// layout if the database must order or compare them.
+impl<T, DB: Database> Type<DB> for Id<T>
+where
+    i64: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i64 as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i64 as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, T, DB: Database> Encode<'q, DB> for Id<T>
+where
+    i64: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.as_i64().encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_i64().size_hint()
+    }
+}
+
+impl<'r, T, DB: Database> Decode<'r, DB> for Id<T>
+where
+    i64: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <i64 as Decode<DB>>::decode(value)?;
+        Id::from_i64(value).ok_or_else(|| ParseIdError::Zero.into())
+    }
+}
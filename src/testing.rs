@@ -0,0 +1,108 @@
+//! Utilities for deterministic tests of code that generates IDs.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::clock::TimeSource;
+
+/// Manually driven [`TimeSource`].
+///
+/// Clones share the same time, so keep one handle after passing another to
+/// [`IdGeneratorBuilder::time_source`](crate::IdGeneratorBuilder::time_source).
+/// Sleeping advances the clock instead of blocking, so sequence exhaustion
+/// rolls straight over into the next tick.
+///
+/// ```
+/// use banuid::testing::MockClock;
+/// use banuid::IdGenerator;
+///
+/// let clock = MockClock::new(1_704_067_200_000);
+/// let generator = IdGenerator::builder()
+///     .time_source(clock.clone())
+///     .shard_id(1)
+///     .build()
+///     .unwrap();
+///
+/// let first = generator.next_id();
+/// clock.advance(250);
+/// let later = generator.next_id();
+/// let layout = generator.layout();
+/// assert_eq!(layout.extract_timestamp(later) - layout.extract_timestamp(first), 250);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    micros: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    /// Clock reading `ms` milliseconds since the Unix epoch.
+    pub fn new(ms: u64) -> Self {
+        MockClock {
+            micros: Arc::new(AtomicU64::new(ms * 1_000)),
+        }
+    }
+
+    /// Move the clock forward by `ms` milliseconds.
+    pub fn advance(&self, ms: u64) {
+        self.micros.fetch_add(ms * 1_000, Ordering::SeqCst);
+    }
+
+    /// Set the clock to `ms` milliseconds since the Unix epoch, possibly moving it backwards.
+    pub fn set(&self, ms: u64) {
+        self.micros.store(ms * 1_000, Ordering::SeqCst);
+    }
+
+    /// Current reading in milliseconds since the Unix epoch.
+    pub fn now_ms(&self) -> u64 {
+        self.micros.load(Ordering::SeqCst) / 1_000
+    }
+}
+
+impl TimeSource for MockClock {
+    fn now(&self) -> Duration {
+        Duration::from_micros(self.micros.load(Ordering::SeqCst))
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.micros
+            .fetch_add(duration.as_micros() as u64, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IdGenerator;
+
+    #[test]
+    fn test_mock_clock_sequence_rollover() {
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 10);
+        let generator = IdGenerator::builder()
+            .timestamp_bits(44)
+            .shard_bits(16)
+            .sequence_bits(4)
+            .time_source(clock.clone())
+            .shard_id(9)
+            .build()
+            .unwrap();
+        let layout = generator.layout();
+
+        let ids: Vec<u64> = (0..17).map(|_| generator.next_id()).collect();
+        assert_eq!(layout.extract_raw_timestamp(ids[15]), 10);
+        assert_eq!(layout.extract_sequence(ids[15]), 15);
+        assert_eq!(layout.extract_raw_timestamp(ids[16]), 11);
+        assert_eq!(layout.extract_sequence(ids[16]), 0);
+        assert_eq!(clock.now_ms(), crate::CUSTOM_EPOCH + 11);
+    }
+
+    #[test]
+    fn test_mock_clock_set_and_advance() {
+        let clock = MockClock::new(1_000);
+        let shared = clock.clone();
+        shared.advance(500);
+        assert_eq!(clock.now_ms(), 1_500);
+        clock.set(200);
+        assert_eq!(shared.now(), Duration::from_millis(200));
+    }
+}
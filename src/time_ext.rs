@@ -0,0 +1,59 @@
+use time::OffsetDateTime;
+
+use crate::builder::IdGeneratorBuilder;
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+impl Layout {
+    /// Creation time of `id` in UTC, or `None` if it lies outside what `time` can represent.
+    pub fn extract_offset_datetime(&self, id: u64) -> Option<OffsetDateTime> {
+        let nanos = self.extract_timestamp(id) as i128 * 1_000_000;
+        OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+    }
+}
+
+impl IdGenerator {
+    /// Creation time of an ID minted with the default layout.
+    pub fn extract_offset_datetime(id: u64) -> OffsetDateTime {
+        Layout::DEFAULT
+            .extract_offset_datetime(id)
+            .expect("default layout timestamps are always representable")
+    }
+}
+
+impl IdGeneratorBuilder {
+    /// Measure timestamps from `epoch`, truncated to whole milliseconds.
+    pub fn epoch_datetime(self, epoch: OffsetDateTime) -> Self {
+        self.epoch(epoch.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BuildError;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_extract_offset_datetime() {
+        let id = Layout::DEFAULT.encode(1_500, 7, 0);
+        assert_eq!(
+            IdGenerator::extract_offset_datetime(id),
+            datetime!(2024-01-01 00:00:01.5 UTC)
+        );
+    }
+
+    #[test]
+    fn test_builder_epoch_datetime() {
+        let generator = IdGenerator::builder()
+            .epoch_datetime(datetime!(2020-01-01 00:00 +02:00))
+            .build()
+            .unwrap();
+        assert_eq!(generator.layout().epoch(), 1_577_829_600_000);
+
+        let result = IdGenerator::builder()
+            .epoch_datetime(datetime!(1969-12-31 23:59 UTC))
+            .build();
+        assert!(matches!(result, Err(BuildError::EpochBeforeUnixEpoch)));
+    }
+}
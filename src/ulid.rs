@@ -0,0 +1,274 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::crockford::{decode_symbol, CROCKFORD_ALPHABET};
+use crate::id::Id;
+use crate::layout::Layout;
+use crate::IdGenerator;
+
+const ULID_LEN: usize = 26;
+const RANDOM_BITS: u32 = 80;
+const MAX_RANDOM: u128 = (1 << RANDOM_BITS) - 1;
+const MAX_TIMESTAMP: u64 = (1 << 48) - 1;
+
+/// A ULID: 48-bit Unix millisecond timestamp followed by 80 bits of payload,
+/// rendered as 26 Crockford Base32 characters.
+///
+/// ULIDs built from banuid IDs carry the ID's shard and sequence bits at the
+/// top of the payload, so they stay monotonic within a generator and convert
+/// back to the original ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Ulid(u128);
+
+impl Ulid {
+    /// Build a ULID from a Unix millisecond timestamp and 80 payload bits.
+    pub const fn new(timestamp_ms: u64, random: u128) -> Self {
+        Ulid((((timestamp_ms & MAX_TIMESTAMP) as u128) << RANDOM_BITS) | (random & MAX_RANDOM))
+    }
+
+    pub const fn from_u128(value: u128) -> Self {
+        Ulid(value)
+    }
+
+    pub const fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// Milliseconds since the Unix epoch
+    pub const fn timestamp(&self) -> u64 {
+        (self.0 >> RANDOM_BITS) as u64
+    }
+
+    /// The 80-bit payload
+    pub const fn random(&self) -> u128 {
+        self.0 & MAX_RANDOM
+    }
+
+    /// Convert an ID from the default layout, keeping its timestamp.
+    pub fn from_id(id: u64) -> Self {
+        Layout::DEFAULT.to_ulid(id).unwrap()
+    }
+
+    /// Recover the default-layout ID this ULID was built from.
+    pub fn to_id(&self) -> u64 {
+        let layout = Layout::DEFAULT;
+        let low_bits = 64 - layout.timestamp_bits() as u32;
+        let elapsed = self.timestamp().saturating_sub(layout.epoch());
+        let low = (self.random() >> (RANDOM_BITS - low_bits)) as u64;
+        (elapsed << low_bits) | low
+    }
+
+    // The version and the bits below the timestamp lead the payload, `fill`
+    // pads the rest. None for layouts without a timestamp.
+    pub(crate) fn from_id_with_layout(id: u64, layout: &Layout, fill: u128) -> Option<Self> {
+        if layout.timestamp_bits() == 0 {
+            return None;
+        }
+        // At most 63 bits, as the timestamp takes at least one
+        let shift = layout.timestamp_shift();
+        let low = id & ((1u64 << shift) - 1);
+        let carried = ((layout.extract_version(id) as u128) << shift) | low as u128;
+        let fill_bits = RANDOM_BITS - (layout.version_bits() as u32 + shift);
+        let fill = fill & ((1u128 << fill_bits) - 1);
+        Some(Ulid::new(
+            layout.extract_timestamp(id),
+            (carried << fill_bits) | fill,
+        ))
+    }
+}
+
+impl IdGenerator {
+    /// Generate a ULID from this generator's clock and sequence state.
+    ///
+    /// Bits not taken by the underlying ID are filled with randomness.
+    pub fn next_ulid(&self) -> Ulid {
+        let id = self.next_id();
+        let fill =
+            ((crate::random::random_u64() as u128) << 64) | crate::random::random_u64() as u128;
+        // Generator layouts always have a timestamp
+        Ulid::from_id_with_layout(id, self.layout(), fill).unwrap()
+    }
+}
+
+impl Layout {
+    /// Convert an ID minted with this layout to a ULID carrying its timestamp.
+    ///
+    /// The payload starts with the ID's remaining bits and is zero-padded, so
+    /// the same ID always yields the same ULID. `None` if the layout has no
+    /// timestamp field.
+    pub fn to_ulid(&self, id: u64) -> Option<Ulid> {
+        Ulid::from_id_with_layout(id, self, 0)
+    }
+}
+
+impl IdGenerator {
+    /// Render a default-layout ID as a ULID string, for dashboards and tools
+    /// that expect ULIDs. See [`Ulid::from_id`].
+    ///
+    /// ```
+    /// use banuid::{IdGenerator, Ulid};
+    ///
+    /// let id = IdGenerator::with_shard_id(5).next_id();
+    /// let ulid = IdGenerator::to_ulid_string(id);
+    /// assert_eq!(ulid.len(), 26);
+    /// assert_eq!(ulid.parse::<Ulid>().unwrap().to_id(), id);
+    /// ```
+    pub fn to_ulid_string(id: u64) -> String {
+        Ulid::from_id(id).to_string()
+    }
+}
+
+impl<T> Id<T> {
+    /// The ID as a ULID string, see [`IdGenerator::to_ulid_string`].
+    pub fn to_ulid_string(&self) -> String {
+        IdGenerator::to_ulid_string(self.as_u64())
+    }
+}
+
+impl From<u128> for Ulid {
+    fn from(value: u128) -> Self {
+        Ulid(value)
+    }
+}
+
+impl From<Ulid> for u128 {
+    fn from(ulid: Ulid) -> Self {
+        ulid.0
+    }
+}
+
+impl fmt::Display for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; ULID_LEN];
+        let mut value = self.0;
+        for slot in buf.iter_mut().rev() {
+            *slot = CROCKFORD_ALPHABET[(value & 0x1F) as usize];
+            value >>= 5;
+        }
+        // Only ASCII from the alphabet was written
+        f.write_str(std::str::from_utf8(&buf).unwrap())
+    }
+}
+
+impl FromStr for Ulid {
+    type Err = ParseUlidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != ULID_LEN {
+            return Err(ParseUlidError::InvalidLength(s.len()));
+        }
+
+        let bytes = s.as_bytes();
+        // 26 characters hold 130 bits, so the leading one may only use 3
+        if decode_symbol(bytes[0]).is_some_and(|v| v > 7) {
+            return Err(ParseUlidError::Overflow);
+        }
+
+        let mut value: u128 = 0;
+        for &byte in bytes {
+            let digit = decode_symbol(byte).ok_or(ParseUlidError::InvalidChar(byte as char))?;
+            value = (value << 5) | digit as u128;
+        }
+        Ok(Ulid(value))
+    }
+}
+
+/// Errors returned when parsing a [`Ulid`] from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseUlidError {
+    /// ULIDs are exactly 26 characters long.
+    InvalidLength(usize),
+    /// The character is not part of the Crockford Base32 alphabet.
+    InvalidChar(char),
+    /// The value does not fit in 128 bits.
+    Overflow,
+}
+
+impl fmt::Display for ParseUlidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseUlidError::InvalidLength(len) => {
+                write!(f, "ULID must be 26 characters, got {}", len)
+            }
+            ParseUlidError::InvalidChar(c) => write!(f, "invalid ULID character {:?}", c),
+            ParseUlidError::Overflow => write!(f, "ULID exceeds 128 bits"),
+        }
+    }
+}
+
+impl std::error::Error for ParseUlidError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ulid_string_round_trip() {
+        let ulid = Ulid::new(1_469_918_176_385, 0x0123_4567_89AB_CDEF_0123);
+        let encoded = ulid.to_string();
+
+        assert_eq!(encoded.len(), 26);
+        assert_eq!(encoded.parse::<Ulid>(), Ok(ulid));
+        assert_eq!(encoded.to_lowercase().parse::<Ulid>(), Ok(ulid));
+        assert_eq!(
+            "01ARYZ6S41TSV4RRFFQ69G5FAV"
+                .parse::<Ulid>()
+                .unwrap()
+                .timestamp(),
+            1_469_918_176_385
+        );
+        assert_eq!(
+            "81ARYZ6S41TSV4RRFFQ69G5FAV".parse::<Ulid>(),
+            Err(ParseUlidError::Overflow)
+        );
+        assert!(matches!(
+            "01ARYZ6S41TSV4RRFFQ69G5FAU".parse::<Ulid>(),
+            Err(ParseUlidError::InvalidChar('U'))
+        ));
+    }
+
+    #[test]
+    fn test_ulid_id_conversion() {
+        let generator = IdGenerator::with_shard_id(42);
+        let id = generator.next_id();
+        let ulid = Ulid::from_id(id);
+
+        assert_eq!(ulid.timestamp(), IdGenerator::extract_timestamp(id));
+        assert_eq!(ulid.to_id(), id);
+        assert_eq!(IdGenerator::to_ulid_string(id), ulid.to_string());
+        assert_eq!(Layout::DEFAULT.to_ulid(id), Some(ulid));
+
+        let layout = *IdGenerator::snowflake(1, 1).layout();
+        let ulid = layout.to_ulid(id).unwrap();
+        assert_eq!(ulid.timestamp(), layout.extract_timestamp(id));
+        assert_eq!(ulid.random() & 0xFF, 0);
+
+        // The version above the timestamp is carried in the payload
+        let versioned = Layout::DEFAULT.with_version(3, 5).unwrap();
+        let id = versioned.encode(1_000, 42, 7);
+        let ulid = versioned.to_ulid(id).unwrap();
+        assert_eq!(ulid.timestamp(), versioned.extract_timestamp(id));
+        let carried = (ulid.random() >> (RANDOM_BITS - 3 - versioned.timestamp_shift())) as u64;
+        assert_eq!(carried >> versioned.timestamp_shift(), 5);
+        assert_eq!(
+            carried,
+            (5 << versioned.timestamp_shift()) | (id & 0x7F_FFFF)
+        );
+
+        let untimed = Layout::new_short(0, 16, 16).unwrap();
+        assert_eq!(untimed.to_ulid(1), None);
+    }
+
+    #[test]
+    fn test_generated_ulids_are_monotonic() {
+        let generator = IdGenerator::with_shard_id(7);
+        let ulids: Vec<Ulid> = (0..100).map(|_| generator.next_ulid()).collect();
+
+        assert!(ulids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(IdGenerator::extract_shard_id(ulids[0].to_id()), 7);
+    }
+}
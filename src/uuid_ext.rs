@@ -0,0 +1,101 @@
+use uuid::Uuid;
+
+use crate::id::Id;
+use crate::IdGenerator;
+
+// UUIDv8 fields as bit offsets from the least significant end of the u128
+const VERSION_SHIFT: u32 = 76;
+const VARIANT_SHIFT: u32 = 62;
+const LOW_BITS_SHIFT: u32 = 58;
+
+impl IdGenerator {
+    /// Embed `id` in a UUIDv8, for systems that require 128-bit UUID columns.
+    ///
+    /// The ID's bits fill the custom fields from the most significant end,
+    /// skipping the version and variant bits, so the UUIDs sort like the IDs.
+    /// The remaining 58 bits are zero.
+    ///
+    /// ```
+    /// use banuid::IdGenerator;
+    ///
+    /// let id = IdGenerator::with_shard_id(3).next_id();
+    /// let uuid = IdGenerator::to_uuid(id);
+    /// assert_eq!(uuid.get_version_num(), 8);
+    /// assert_eq!(IdGenerator::from_uuid(&uuid), Some(id));
+    /// ```
+    pub fn to_uuid(id: u64) -> Uuid {
+        let id = id as u128;
+        let value = ((id >> 16) << 80)
+            | (8 << VERSION_SHIFT)
+            | (((id >> 4) & 0xFFF) << 64)
+            | (0b10 << VARIANT_SHIFT)
+            | ((id & 0xF) << LOW_BITS_SHIFT);
+        Uuid::from_u128(value)
+    }
+
+    /// Recover an ID embedded by [`to_uuid`](Self::to_uuid), or `None` if
+    /// `uuid` was not produced by it.
+    pub fn from_uuid(uuid: &Uuid) -> Option<u64> {
+        let value = uuid.as_u128();
+        let is_embedded = (value >> VERSION_SHIFT) & 0xF == 8
+            && (value >> VARIANT_SHIFT) & 0b11 == 0b10
+            && value & ((1 << LOW_BITS_SHIFT) - 1) == 0;
+        if !is_embedded {
+            return None;
+        }
+        let id = ((value >> 80) << 16)
+            | (((value >> 64) & 0xFFF) << 4)
+            | ((value >> LOW_BITS_SHIFT) & 0xF);
+        Some(id as u64)
+    }
+}
+
+impl<T> Id<T> {
+    /// The ID embedded in a UUIDv8, see [`IdGenerator::to_uuid`].
+    pub fn to_uuid(&self) -> Uuid {
+        IdGenerator::to_uuid(self.as_u64())
+    }
+
+    /// Recover an ID embedded by [`to_uuid`](Self::to_uuid).
+    pub fn from_uuid(uuid: &Uuid) -> Option<Self> {
+        IdGenerator::from_uuid(uuid).and_then(Self::from_u64)
+    }
+}
+
+impl<T> From<Id<T>> for Uuid {
+    fn from(id: Id<T>) -> Self {
+        id.to_uuid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Variant;
+
+    #[test]
+    fn test_uuid_round_trip() {
+        let ids = [0, 1, 0xF, 0x10, 1 << 40, u64::MAX - 1, u64::MAX];
+        for pair in ids.windows(2) {
+            assert!(IdGenerator::to_uuid(pair[0]) < IdGenerator::to_uuid(pair[1]));
+        }
+        for id in ids {
+            let uuid = IdGenerator::to_uuid(id);
+            assert_eq!(uuid.get_version_num(), 8);
+            assert_eq!(uuid.get_variant(), Variant::RFC4122);
+            assert_eq!(IdGenerator::from_uuid(&uuid), Some(id));
+        }
+        assert_eq!(
+            IdGenerator::to_uuid(0x0123_4567_89AB_CDEF).to_string(),
+            "01234567-89ab-8cde-bc00-000000000000"
+        );
+
+        assert_eq!(IdGenerator::from_uuid(&Uuid::nil()), None);
+        let low_bit_set = Uuid::from_u128(IdGenerator::to_uuid(7).as_u128() | 1);
+        assert_eq!(IdGenerator::from_uuid(&low_bit_set), None);
+
+        let id: Id = Id::from_u64(42).unwrap();
+        assert_eq!(Id::from_uuid(&Uuid::from(id)), Some(id));
+        assert_eq!(Id::<()>::from_uuid(&IdGenerator::to_uuid(0)), None);
+    }
+}
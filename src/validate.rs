@@ -0,0 +1,189 @@
+use std::fmt;
+use std::ops::RangeBounds;
+use std::time::Duration;
+
+use crate::{IdComponents, IdGenerator, Layout};
+
+/// How far ahead of the local clock an ID may be before it is rejected.
+const MAX_FUTURE: Duration = Duration::from_secs(60);
+
+/// Reasons an ID fails [`Layout::validate`] or [`Layout::decode_strict`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvalidId {
+    /// The ID is zero, which no generator ever issues.
+    Zero,
+    /// Bits above the layout's width are set.
+    UnusedBitsSet,
+    /// The version bits do not match the layout's version.
+    VersionMismatch { expected: u8, found: u8 },
+    /// The checksum bits do not match the rest of the ID.
+    ChecksumMismatch,
+    /// The timestamp is further ahead of the local clock than clock skew explains.
+    FromFuture { ahead_ms: u64 },
+    /// The timestamp falls outside the accepted window.
+    OutsideWindow { timestamp_ms: u64 },
+}
+
+impl fmt::Display for InvalidId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidId::Zero => write!(f, "ID is zero"),
+            InvalidId::UnusedBitsSet => write!(f, "ID sets bits outside the layout"),
+            InvalidId::VersionMismatch { expected, found } => {
+                write!(f, "ID has version {}, expected {}", found, expected)
+            }
+            InvalidId::ChecksumMismatch => write!(f, "ID checksum does not match"),
+            InvalidId::FromFuture { ahead_ms } => {
+                write!(f, "ID timestamp is {} ms in the future", ahead_ms)
+            }
+            InvalidId::OutsideWindow { timestamp_ms } => {
+                write!(
+                    f,
+                    "ID timestamp {} is outside the accepted window",
+                    timestamp_ms
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidId {}
+
+impl Layout {
+    /// Check that `id` could have been minted under this layout by `now_ms`
+    /// (milliseconds since the Unix epoch), allowing a minute of clock skew.
+    ///
+    /// Catches garbage input at API boundaries; it cannot prove that an ID was
+    /// actually issued.
+    pub fn validate(&self, id: u64, now_ms: u64) -> Result<(), InvalidId> {
+        self.check_bits(id)?;
+        let ahead_ms = self.extract_timestamp(id).saturating_sub(now_ms);
+        if ahead_ms > MAX_FUTURE.as_millis() as u64 {
+            return Err(InvalidId::FromFuture { ahead_ms });
+        }
+        Ok(())
+    }
+
+    /// Decode `id`, rejecting it if any bit outside the encoded fields is set,
+    /// its version or checksum does not match, or its timestamp (milliseconds
+    /// since the Unix epoch) falls outside `window`.
+    ///
+    /// [`Layout::decode`] is the lenient counterpart, slicing bits without checks.
+    pub fn decode_strict(
+        &self,
+        id: u64,
+        window: impl RangeBounds<u64>,
+    ) -> Result<IdComponents, InvalidId> {
+        self.check_bits(id)?;
+        let components = self.decode(id);
+        if !window.contains(&components.timestamp_ms) {
+            return Err(InvalidId::OutsideWindow {
+                timestamp_ms: components.timestamp_ms,
+            });
+        }
+        Ok(components)
+    }
+
+    fn check_bits(&self, id: u64) -> Result<(), InvalidId> {
+        if id == 0 {
+            return Err(InvalidId::Zero);
+        }
+        if self.total_bits() < 64 && id >> self.total_bits() != 0 {
+            return Err(InvalidId::UnusedBitsSet);
+        }
+        if self.extract_version(id) != self.version() {
+            return Err(InvalidId::VersionMismatch {
+                expected: self.version(),
+                found: self.extract_version(id),
+            });
+        }
+        if !self.verify_checksum(id) {
+            return Err(InvalidId::ChecksumMismatch);
+        }
+        Ok(())
+    }
+}
+
+impl IdGenerator {
+    /// Check that `id` could have been minted under this generator's layout,
+    /// see [`Layout::validate`].
+    pub fn validate(&self, id: u64) -> Result<(), InvalidId> {
+        let now_ms = self.clock.now().as_millis() as u64;
+        self.layout().validate(id, now_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockClock;
+
+    #[test]
+    fn test_validate() {
+        let clock = MockClock::new(crate::CUSTOM_EPOCH + 10_000);
+        let generator = IdGenerator::builder()
+            .time_source(clock.clone())
+            .build()
+            .unwrap();
+        let layout = *generator.layout();
+
+        let id = generator.next_id();
+        assert_eq!(generator.validate(id), Ok(()));
+        assert_eq!(generator.validate(0), Err(InvalidId::Zero));
+        assert_eq!(
+            generator.validate(layout.encode(100_000, 1, 1)),
+            Err(InvalidId::FromFuture { ahead_ms: 90_000 })
+        );
+        assert_eq!(generator.validate(layout.encode(60_000, 1, 1)), Ok(()));
+
+        let narrow = Layout::new(40, 10, 13).unwrap();
+        assert_eq!(
+            narrow.validate(1 << 63, u64::MAX),
+            Err(InvalidId::UnusedBitsSet)
+        );
+
+        let versioned = Layout::DEFAULT.with_version(2, 1).unwrap();
+        assert_eq!(
+            versioned.validate(Layout::DEFAULT.encode(5, 1, 1), u64::MAX),
+            Err(InvalidId::VersionMismatch {
+                expected: 1,
+                found: 0
+            })
+        );
+
+        let checked = Layout::DEFAULT.with_checksum(4).unwrap();
+        let id = checked.encode(5, 1, 1);
+        assert_eq!(checked.validate(id, u64::MAX), Ok(()));
+        assert_eq!(
+            checked.validate(id ^ (1 << 20), u64::MAX),
+            Err(InvalidId::ChecksumMismatch)
+        );
+
+        assert!(crate::validate(crate::generate()).is_ok());
+    }
+
+    #[test]
+    fn test_decode_strict() {
+        let layout = Layout::DEFAULT.with_version(1, 1).unwrap();
+        let id = layout.encode(1_000, 2, 3);
+        let timestamp_ms = crate::CUSTOM_EPOCH + 1_000;
+
+        assert_eq!(layout.decode_strict(id, ..), Ok(layout.decode(id)));
+        assert_eq!(
+            layout.decode_strict(id, timestamp_ms..=timestamp_ms),
+            Ok(layout.decode(id))
+        );
+        assert_eq!(
+            layout.decode_strict(id, timestamp_ms + 1..),
+            Err(InvalidId::OutsideWindow { timestamp_ms })
+        );
+        assert_eq!(
+            layout.decode_strict(id & !(1 << 63), ..),
+            Err(InvalidId::VersionMismatch {
+                expected: 1,
+                found: 0
+            })
+        );
+    }
+}
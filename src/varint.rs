@@ -0,0 +1,137 @@
+use std::fmt;
+
+/// Bytes needed for any `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+/// Append `value` to `out` as unsigned LEB128, 7 bits per byte with the high
+/// bit marking continuation, returning the number of bytes written.
+///
+/// Raw IDs carry their timestamp in the high bits and take 9 bytes; the
+/// encoding pays off for the differences written by [`encode_deltas`] or
+/// other small values such as [`Layout::encode`](crate::Layout::encode)
+/// output near the epoch.
+///
+/// ```
+/// let mut out = Vec::new();
+/// assert_eq!(banuid::encode_varint(300, &mut out), 2);
+/// assert_eq!(out, [0xAC, 0x02]);
+/// assert_eq!(banuid::decode_varint(&out), Ok((300, 2)));
+/// ```
+pub fn encode_varint(value: u64, out: &mut Vec<u8>) -> usize {
+    let start = out.len();
+    let mut value = value;
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+    out.len() - start
+}
+
+/// Read one LEB128 value from the start of `bytes`, returning it with the
+/// number of bytes consumed.
+pub fn decode_varint(bytes: &[u8]) -> Result<(u64, usize), VarintError> {
+    let mut value = 0u64;
+    for (i, &byte) in bytes.iter().take(MAX_VARINT_LEN).enumerate() {
+        let bits = (byte & 0x7F) as u64;
+        // The tenth byte holds only the 64th bit
+        if i == MAX_VARINT_LEN - 1 && byte > 1 {
+            return Err(VarintError::Overflow);
+        }
+        value |= bits << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(VarintError::Truncated)
+}
+
+/// Encode IDs as LEB128 differences from the previous ID, the first from
+/// zero.
+///
+/// Sorted IDs from one generator differ by small amounts, so each usually
+/// takes 2 to 4 bytes instead of 8. Unsorted input still round-trips, just
+/// without the savings.
+///
+/// ```
+/// let generator = banuid::IdGenerator::with_shard_id(1);
+/// let ids: Vec<u64> = (0..100).map(|_| generator.next_id()).collect();
+/// let encoded = banuid::encode_deltas(&ids);
+/// assert!(encoded.len() < ids.len() * 8);
+/// assert_eq!(banuid::decode_deltas(&encoded), Ok(ids));
+/// ```
+pub fn encode_deltas(ids: &[u64]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(ids.len() * 2);
+    let mut previous = 0u64;
+    for &id in ids {
+        encode_varint(id.wrapping_sub(previous), &mut out);
+        previous = id;
+    }
+    out
+}
+
+/// Decode IDs written by [`encode_deltas`].
+pub fn decode_deltas(bytes: &[u8]) -> Result<Vec<u64>, VarintError> {
+    let mut ids = Vec::new();
+    let mut previous = 0u64;
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let (delta, len) = decode_varint(rest)?;
+        previous = previous.wrapping_add(delta);
+        ids.push(previous);
+        rest = &rest[len..];
+    }
+    Ok(ids)
+}
+
+/// Error decoding LEB128 bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VarintError {
+    /// The input ended before the last byte of a value.
+    Truncated,
+    /// The value does not fit in 64 bits.
+    Overflow,
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarintError::Truncated => write!(f, "varint is truncated"),
+            VarintError::Overflow => write!(f, "varint exceeds 64 bits"),
+        }
+    }
+}
+
+impl std::error::Error for VarintError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint_round_trip() {
+        for (value, len) in [(0, 1), (127, 1), (128, 2), (1 << 56, 9), (u64::MAX, 10)] {
+            let mut out = Vec::new();
+            assert_eq!(encode_varint(value, &mut out), len);
+            assert_eq!(decode_varint(&out), Ok((value, len)));
+        }
+        assert_eq!(decode_varint(&[]), Err(VarintError::Truncated));
+        assert_eq!(decode_varint(&[0x80, 0x80]), Err(VarintError::Truncated));
+        let mut too_big = vec![0xFF; 9];
+        too_big.push(0x02);
+        assert_eq!(decode_varint(&too_big), Err(VarintError::Overflow));
+    }
+
+    #[test]
+    fn test_deltas() {
+        let ids = [1 << 60, (1 << 60) + 1, (1 << 60) + 4096, 5];
+        let encoded = encode_deltas(&ids);
+        assert_eq!(decode_deltas(&encoded), Ok(ids.to_vec()));
+        assert_eq!(decode_deltas(&[]), Ok(vec![]));
+        assert_eq!(
+            decode_deltas(&encoded[..encoded.len() - 1]),
+            Err(VarintError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! Binds and decodes IDs through a real driver. Needs SQLite and a runtime,
+//! so it only runs with `--features sqlx-sqlite-test`.
+
+use banuid::Id;
+use sqlx::{Connection, SqliteConnection};
+
+#[tokio::test]
+async fn test_sqlx_round_trip() {
+    struct Order;
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    for value in [1u64 << 40, u64::MAX] {
+        let id: Id<Order> = Id::from_u64(value).unwrap();
+        let stored: i64 = sqlx::query_scalar("SELECT ?")
+            .bind(id)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(stored, value as i64);
+
+        let restored: Id<Order> = sqlx::query_scalar("SELECT ?")
+            .bind(id)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(restored, id);
+    }
+
+    let zero = sqlx::query_scalar::<_, Id>("SELECT 0")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(zero.is_err());
+}